@@ -1,18 +1,26 @@
-use super::lint::{Level, LintResult, Violation};
+use super::lint::{rules, Level, LintResult, Rules, Violation};
+use super::parser::{self, Footer};
 use super::CommitType;
-use crate::Version;
-use lazy_static::lazy_static;
+use crate::{Bump, Version};
 use log::trace;
-use regex::Regex;
-use std::collections::HashMap;
+use std::fmt;
 
-type CommitBody = (Option<String>, Option<HashMap<String, String>>);
+/// Width at which the lines of the body are wrapped when formatting a commit, matches the
+/// default of the `body-max-line-length` rule
+const BODY_WIDTH: usize = 100;
+
+/// Footer tokens (in lower case) of the directive that ignores rules for a commit message
+const IGNORE_TOKENS: &[&str] = &["cocors-ignore", "cocors-disable"];
+
+/// Upper bound of lint and fix passes, in case fixes of different rules undo each other
+const MAX_FIX_PASSES: usize = 5;
 
 /// Represents a commit message according to the
 /// [conventional commit specification](https://www.conventionalcommits.org/en/v1.0.0/#specification)
 ///
 /// Can be used to bump/ rollback a semantic version and to generate changelogs.
-#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Eq, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Commit {
     /// Defines if the changes in the commit are breaking the backwards compatibility
     /// of the Public API (annotated by `!` after the type or BREAKING CHANGE type)
@@ -20,242 +28,573 @@ pub struct Commit {
     /// Type of the changes made in the commit that are used to bump the version
     /// (e.g. `fix`, `feat` or `BREAKING CHANGE`) or types that do not affect the versioning (e.g. `docs`, `chore`)
     pub commit_type: CommitType,
-    /// Optional scope, that defines where the changes in the code happened (e.g. parser)
-    pub scope: Option<String>,
+    /// Optional scopes, that define where the changes in the code happened (e.g. parser),
+    /// multiple scopes are separated by a comma (e.g. `feat(api,cli): ...`)
+    pub scopes: Vec<String>,
     /// A short string summarizing the changes in the commit
     pub description: String,
     pub body: Option<String>,
-    pub footer: Option<HashMap<String, String>>,
+    /// The footers as token and value in the order they appear in the message
+    /// (e.g. `("Refs", "#123")`), values given as `Refs #123` contain the `#`
+    pub footer: Option<Vec<(String, String)>>,
 }
 
 impl Commit {
     /// Takes a commit string and parses it according to the conventional commit
     /// specification.
     ///
-    /// The function uses regex to extract all relevant tags according to the
-    /// specification, if the commit message does not conform `None` will
-    /// be returned.
+    /// The function uses the [parser](crate::parser) to extract all relevant tags according
+    /// to the specification, if the commit message does not conform `None` will be returned.
     pub fn parse(commit: &str) -> Option<Commit> {
         Commit::lint(commit).commit
     }
 
-    /// Bumps the given version according to the commit message
-    pub fn bump(&self, version: &mut Version) {
+    /// The level by which the commit increments the version
+    pub fn bump_level(&self) -> Bump {
         if self.breaking {
-            let major = version.major + 1;
-
-            version.reset();
-            version.major = major;
-            return;
-        }
-        match self.commit_type {
-            CommitType::Fix => version.patch += 1,
-            CommitType::Feature => {
-                version.minor += 1;
-                version.patch = 0;
-            }
-            CommitType::BreakingChange => {
-                version.major += 1;
-                version.minor = 0;
-                version.patch = 0
-            }
-            _ => return,
+            return Bump::Major;
         }
+        self.commit_type.bump()
+    }
 
-        version.pre_release = None;
-        version.metadata = None;
+    /// Bumps the given version according to the commit message
+    pub fn bump(&self, version: &mut Version) {
+        version.increment(self.bump_level());
     }
 
+    /// Lints the commit message with the default [Rules]
+    ///
+    /// The returned [LintResult] contains the parsed commit if no violation with the level
+    /// [Level::Error] has been found.
     pub fn lint(commit: &str) -> LintResult {
+        Commit::lint_with(commit, &Rules::default())
+    }
+
+    /// Lints the commit message against the given set of [Rules]
+    ///
+    /// Rules can be ignored for a single message with a `Cocors-Ignore` (or `Cocors-Disable`)
+    /// footer that lists the rules separated by commas, their violations are moved to
+    /// [LintResult::ignored]. Violations of the specification that are reported by the
+    /// [parser](crate::parser) (e.g. `type-empty` or `header-separator`) can not be ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::{lint::Rules, Commit};
+    /// let result = Commit::lint_with(
+    ///     "feat: add parser.\n\nCocors-Ignore: subject-full-stop, scope-missing",
+    ///     &Rules::default(),
+    /// );
+    ///
+    /// assert!(result.lints.is_empty());
+    /// assert_eq!(result.ignored.len(), 2);
+    /// ```
+    pub fn lint_with(commit: &str, rules: &Rules) -> LintResult {
         let mut res = LintResult {
             commit: None,
             lints: Vec::<Violation>::new(),
+            ignored: Vec::<Violation>::new(),
         };
 
-        lazy_static! {
-            static ref COMMIT_RE: Regex =
-                Regex::new(r"([a-z,A-Z]+)?(\((.+)?\))?(!)?(?:: )(.+)?(\n\n(?:.|\n)*)?").unwrap();
-        }
-        let caps_option = COMMIT_RE.captures(commit);
-
-        // return early if the regex did not find anything
-        // TODO: Add specific log message for each failure point for
-        //      later usage in linter
-        if caps_option.is_none() {
-            trace!("Regex did not find any matches for commit message");
-            res.lints.push(Violation {
-                level: Level::Error,
-                message: String::from("The format of the commit message is not conformant to conventional commit specification"),
-                description: Some(String::from(
-                    r#"Make sure that the specification follows the specification:<type>[optional scope]: <description>
+        let parsed = parser::parse(commit);
+        // the commit is built from the parsed message, so these violations must not be ignored
+        let structural = parsed.violations;
+
+        // return early if the header could not be parsed at all
+        let message = match parsed.message {
+            Some(m) => m,
+            None => {
+                trace!("Parser could not parse the header of the commit message");
+                res.lints.extend(structural);
+                return res;
+            }
+        };
 
-                    [optional body]
+        trace!("Parser did parse the commit message");
+        let header = &message.header;
 
-                    [optional footer(s)]"#))
+        if header.scope.is_none() {
+            res.lints.push(Violation {
+                level: Level::Suggestion,
+                rule: String::from("scope-missing"),
+                message: String::from("Optional scope is missing"),
+                description: Some(String::from("Consider adding a scope to the commit message to specify where the changes have been made")),
+                span: None,
+                fix: None,
             });
-            return res;
         }
 
-        trace!("Regex did find matches for commit message");
-        let caps = caps_option.unwrap();
-
-        let commit_type = get_commit_type(&mut res, &caps);
-
-        let scope = get_commit_scope(&mut res, &caps);
+        rules::scope::lint(&mut res, rules, commit, &header.scopes);
+        rules::header::lint(&mut res, rules, commit, header);
+        rules::body::lint(&mut res, rules, commit, &message);
 
-        let header = get_commit_header(&mut res, &caps);
+        if message.body.is_some() && message.footers.is_empty() {
+            res.lints.push(Violation {
+                level: Level::Info,
+                rule: String::from("footer-missing"),
+                message: String::from("No footer found"),
+                description: None,
+                span: None,
+                fix: None,
+            });
+        }
 
-        let body = get_commit_body_footer(&mut res, &caps);
-        let description = body.0;
-        let footer = body.1;
+        let ignored_rules = get_ignored_rules(&message.footers);
+        if !ignored_rules.is_empty() {
+            trace!("Ignoring the rules {:?}", ignored_rules);
+            (res.ignored, res.lints) = res
+                .lints
+                .into_iter()
+                .partition(|l| ignored_rules.contains(&l.rule.as_str()));
+        }
 
+        res.lints.extend(structural);
         res.lints.sort_unstable();
+        res.ignored.sort_unstable();
 
-        if !res.lints.iter().any(|l| l.level == Level::Error)
-            && commit_type.is_some()
-            && header.is_some()
-        {
-            let header_unwrapped = header.unwrap();
-            let commit_type_unwrapped = commit_type.unwrap();
+        if !res.lints.iter().any(|l| l.level == Level::Error) {
+            let commit_type = CommitType::from_token(header.commit_type.text);
 
             res.commit = Some(Commit {
-                breaking: caps.get(4).is_some()
-                    || commit_type_unwrapped == CommitType::BreakingChange,
-                commit_type: commit_type_unwrapped,
-                scope,
-                description: header_unwrapped,
-                body: description,
-                footer: footer,
+                breaking: message.is_breaking() || commit_type == CommitType::BreakingChange,
+                commit_type,
+                scopes: header.scopes.iter().map(|s| s.text.to_string()).collect(),
+                description: header.description.text.to_string(),
+                body: message.body.map(|b| normalize_line_endings(b.text)),
+                footer: get_commit_footer(&message.footers),
             })
         }
 
-        return res;
+        res
     }
-}
 
-fn get_commit_type(result: &mut LintResult, caps: &regex::Captures) -> Option<CommitType> {
-    let commit: Option<CommitType> = match caps.get(1) {
-        None => None,
-        Some(t) => match t.as_str().to_lowercase().as_str() {
-            "fix" => Some(CommitType::Fix),
-            "feat" => Some(CommitType::Feature),
-            "breaking change" => Some(CommitType::BreakingChange),
-            "build" => Some(CommitType::Build),
-            "chore" => Some(CommitType::Chore),
-            "style" => Some(CommitType::Style),
-            "docs" => Some(CommitType::Docs),
-            "refactor" => Some(CommitType::Refactor),
-            "perf" => Some(CommitType::Performance),
-            "test" => Some(CommitType::Test),
-            "ci" => Some(CommitType::Ci),
-            "other" => Some(CommitType::Other),
-            _ => Some(CommitType::Other),
-        },
-    };
-
-    if commit.is_none() {
-        result.lints.push(Violation {
-                level: Level::Error,
-                message: String::from("Mandatory commit type is missing"),
-                description: Some(String::from("Make sure you provide a commit type that describes of what type the change is (e.g. fix, feat, BREAKING CHANGE). Type must be ascii letters only"))
-            });
-    };
+    /// Applies the suggested fixes for violations of the [Rules] to the commit message
+    ///
+    /// Fixing a violation can reveal further violations (e.g. footers are only recognized
+    /// once they are separated from the body), so the message is linted and fixed again
+    /// until no more fixes apply.
+    pub fn fix_with(commit: &str, rules: &Rules) -> String {
+        let mut fixed = String::from(commit);
+
+        for _ in 0..MAX_FIX_PASSES {
+            let next = Commit::lint_with(&fixed, rules).fix(&fixed);
+            if next == fixed {
+                break;
+            }
+            fixed = next;
+        }
 
-    return commit;
+        fixed
+    }
 }
 
-fn get_commit_scope(result: &mut LintResult, caps: &regex::Captures) -> Option<String> {
-    match caps.get(2) {
-        None => {
-            result.lints.push(Violation {
-                level: Level::Suggestion,
-                message: String::from("Optional scope is missing"),
-                description: Some(String::from("Consider adding a scope to the commit message to specify where the changes have been made"))
-            });
-            None
+impl fmt::Display for Commit {
+    /// Formats the commit as conventional commit message
+    ///
+    /// The message is in canonical form: the scopes are separated by a comma, the body is
+    /// wrapped at 100 characters and every footer is written as `token: value`.
+    ///
+    /// Formatting round-trips, `Commit::parse(&commit.to_string()) == Some(commit)` holds for
+    /// every commit returned by [Commit::parse]. A commit built by hand round-trips as well if
+    /// its body is wrapped and, without footers, the last paragraph of the body does not start
+    /// with a footer (e.g. `Refs: #12`), as it is read as footer by the specification.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Commit;
+    /// let message = "feat(api,cli)!: add version command\n\nPrints the version.\n\nRefs: #12";
+    /// let commit = Commit::parse(message).unwrap();
+    ///
+    /// assert_eq!(commit.to_string(), message);
+    /// assert_eq!(Commit::parse(&commit.to_string()), Some(commit));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.commit_type.as_str())?;
+
+        if !self.scopes.is_empty() {
+            write!(f, "({})", self.scopes.join(","))?;
         }
-        Some(_) => {
-            if caps.get(3).is_none() {
-                result.lints.push(Violation {
-                level: Level::Error,
-                message: String::from("Scope is empty"),
-                description: Some(String::from("Scope is an optional parameter, but if not given the parenthesis must be removed"))
-                });
-                None
-            } else {
-                Some(caps.get(3).unwrap().as_str().to_string())
+        if self.breaking && self.commit_type != CommitType::BreakingChange {
+            write!(f, "!")?;
+        }
+        write!(f, ": {}", self.description)?;
+
+        if let Some(body) = &self.body {
+            write!(f, "\n\n{}", wrap(body, BODY_WIDTH))?;
+        }
+
+        if let Some(footer) = self.footer.as_ref().filter(|f| !f.is_empty()) {
+            writeln!(f)?;
+            for (token, value) in footer {
+                write!(f, "\n{}: {}", token, value)?;
             }
         }
+
+        Ok(())
     }
 }
 
-fn get_commit_header(result: &mut LintResult, caps: &regex::Captures) -> Option<String> {
-    match caps.get(5) {
-        None => {
-            result.lints.push(Violation {
-            level: Level::Error,
-            message: String::from("Mandatory description is missing"),
-            description: Some(String::from("The short description of the commit is missing; this is mandatory field and must be provided")),
-        });
-            None
-        }
-        Some(d) => Some(d.as_str().to_string()),
+/// Wraps the lines that are longer than the width at whitespace, lines containing a link
+/// are kept as they are
+fn wrap(text: &str, width: usize) -> String {
+    text.lines()
+        .map(|line| {
+            if line.chars().count() <= width || line.contains("://") {
+                return line.to_string();
+            }
+
+            let mut wrapped = String::new();
+            let mut length = 0;
+
+            for word in line.split(' ') {
+                let word_length = word.chars().count();
+
+                if length > 0 && length + 1 + word_length > width {
+                    wrapped.push('\n');
+                    length = 0;
+                } else if length > 0 {
+                    wrapped.push(' ');
+                    length += 1;
+                }
+
+                wrapped.push_str(word);
+                length += word_length;
+            }
+
+            wrapped
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Collects the rules listed in `Cocors-Ignore` footers, the token is case insensitive
+fn get_ignored_rules<'a>(footers: &[Footer<'a>]) -> Vec<&'a str> {
+    footers
+        .iter()
+        .filter(|f| IGNORE_TOKENS.contains(&f.token.text.to_lowercase().as_str()))
+        .flat_map(|f| f.value.text.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+fn get_commit_footer(footers: &[Footer]) -> Option<Vec<(String, String)>> {
+    if footers.is_empty() {
+        return None;
     }
+
+    Some(
+        footers
+            .iter()
+            .map(|f| {
+                let value = if f.separator.text == " #" {
+                    format!("#{}", f.value.text)
+                } else {
+                    f.value.text.to_string()
+                };
+                (f.token.text.to_string(), normalize_line_endings(&value))
+            })
+            .collect(),
+    )
 }
 
-fn get_commit_body_footer(result: &mut LintResult, caps: &regex::Captures) -> CommitBody {
-    let mut res: CommitBody = (None, None);
+fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::lint::Rules;
+    use crate::{Commit, CommitType};
+
+    #[test]
+    fn commit_type_valid() {
+        let commit_string = "feat: allow provided config object to extend other configs";
 
-    if caps.get(6).is_none() {
-        return res;
+        assert_eq!(
+            Commit::parse(commit_string).map(|c| c.commit_type),
+            Some(CommitType::Feature)
+        );
     }
-    let body = match caps.get(6) {
-        None => return res,
-        Some(m) => m.as_str().to_string(),
-    };
 
-    lazy_static! {
-        static ref BODY_FOOTER_RE: Regex = Regex::new(r"(.*)(?>: )(.*)").unwrap();
+    #[test]
+    fn missing_space_after_colon() {
+        let result = Commit::lint("feat:add parser");
+        let span = result.lints[0].span.unwrap();
+
+        assert_eq!(result.lints[0].rule, "header-separator");
+        assert_eq!((span.start, span.end, span.line, span.column), (4, 5, 1, 5));
     }
 
-    let mut footer = HashMap::<String, String>::new();
+    #[test]
+    fn ignore_directive() {
+        let message = "feat(API): add parser.\n\ncocors-disable: scope-case\nCocors-Ignore: subject-full-stop";
+        let result = Commit::lint(message);
+
+        assert!(result.lints.iter().all(|l| l.rule == "scope-missing"));
+        let mut ignored = result
+            .ignored
+            .iter()
+            .map(|l| l.rule.as_str())
+            .collect::<Vec<&str>>();
+        ignored.sort_unstable();
+        assert_eq!(ignored, vec!["scope-case", "subject-full-stop"]);
+        assert_eq!(result.commit.unwrap().description, "add parser.");
+    }
 
-    let start = match BODY_FOOTER_RE.find(&body) {
-        None => {
-            res.0 = Some(body);
-            result.lints.push(Violation {
-                level: Level::Info,
-                message: String::from("No footer found"),
-                description: None,
-            });
-            return res;
+    #[test]
+    fn ignore_directive_specification() {
+        for (message, rule) in [
+            (
+                "feat(api):add parser\n\nCocors-Ignore: header-separator",
+                "header-separator",
+            ),
+            (
+                "(api): add parser\n\nCocors-Ignore: type-empty",
+                "type-empty",
+            ),
+            (
+                "feat(api:: add parser\n\nCocors-Ignore: scope-format",
+                "scope-format",
+            ),
+            (
+                "feat(api): \n\nCocors-Disable: subject-empty",
+                "subject-empty",
+            ),
+        ] {
+            let result = Commit::lint(message);
+
+            assert!(result.commit.is_none(), "{}", message);
+            assert!(result.lints.iter().any(|l| l.rule == rule), "{}", message);
+            assert!(result.ignored.iter().all(|l| l.rule != rule), "{}", message);
         }
-        Some(m) => m.start(),
-    };
-
-    for cap in BODY_FOOTER_RE.captures_iter(&body) {
-        footer.insert(
-            cap.get(1)
-                .map_or_else(String::new, |k| k.as_str().to_string()),
-            cap.get(2)
-                .map_or_else(String::new, |v| v.as_str().to_string()),
+    }
+
+    #[test]
+    fn fix_with() {
+        assert_eq!(
+            Commit::fix_with(
+                "Fix:remove typo.\r\nThe typo was in the readme\r\nBreaking-Change: typo\r\nReviewed by: Z",
+                &Rules::default()
+            ),
+            "fix: remove typo\r\n\r\nThe typo was in the readme\r\n\r\nBREAKING CHANGE: typo\r\nReviewed-by: Z"
         );
     }
+}
 
-    res.0 = Some(body.split_at(start).0.to_string());
+#[cfg(test)]
+mod format_test {
+
+    use crate::{Commit, CommitType};
+    use proptest::prelude::*;
+
+    #[test]
+    fn format_canonical() {
+        let commit = Commit::parse("Fix(api, cli)!:  remove typo\r\n\r\nIn the readme\r\n\r\nRefs #12\r\nBREAKING CHANGE: none").unwrap();
+
+        assert_eq!(
+            commit.to_string(),
+            "fix(api,cli)!:  remove typo\n\nIn the readme\n\nRefs: #12\nBREAKING CHANGE: none"
+        );
+    }
+
+    #[test]
+    fn format_other_type() {
+        let commit = Commit::parse("deps: bump regex").unwrap();
+
+        assert_eq!(commit.commit_type, CommitType::Other(String::from("deps")));
+        assert_eq!(commit.to_string(), "deps: bump regex");
+    }
+
+    #[test]
+    fn format_breaking_change_type() {
+        let commit = Commit::parse("BREAKING CHANGE: drop the config").unwrap();
+
+        assert_eq!(commit.to_string(), "BREAKING CHANGE: drop the config");
+    }
+
+    #[test]
+    fn format_body_with_colon() {
+        let commit = Commit {
+            commit_type: CommitType::Fix,
+            description: String::from("prevent racing"),
+            body: Some(String::from("Note that: the id is required\n\nSee also #12")),
+            ..Commit::default()
+        };
+
+        assert_eq!(Commit::parse(&commit.to_string()), Some(commit));
+    }
+
+    #[test]
+    fn format_wraps_body() {
+        let commit = Commit {
+            commit_type: CommitType::Docs,
+            description: String::from("explain the parser"),
+            body: Some(format!(
+                "{}\n\nSee https://{}",
+                "word ".repeat(30).trim(),
+                "a".repeat(100)
+            )),
+            ..Commit::default()
+        };
 
-    return res;
+        let message = commit.to_string();
+        let body: Vec<&str> = message.lines().skip(2).collect();
+
+        assert_eq!(body[0].len(), 99);
+        assert_eq!(body[1].len(), 49);
+        assert_eq!(body[3].len(), 112);
+    }
+
+    fn commit_type() -> impl Strategy<Value = CommitType> {
+        prop_oneof![
+            Just(CommitType::Fix),
+            Just(CommitType::Feature),
+            Just(CommitType::BreakingChange),
+            Just(CommitType::Build),
+            Just(CommitType::Chore),
+            Just(CommitType::Ci),
+            Just(CommitType::Docs),
+            Just(CommitType::Style),
+            Just(CommitType::Refactor),
+            Just(CommitType::Performance),
+            Just(CommitType::Test),
+            "[a-zA-Z]{1,10}"
+                .prop_filter("must not be a known type", |t| {
+                    !matches!(
+                        t.to_lowercase().as_str(),
+                        "fix"
+                            | "feat"
+                            | "build"
+                            | "chore"
+                            | "ci"
+                            | "docs"
+                            | "style"
+                            | "refactor"
+                            | "perf"
+                            | "test"
+                    )
+                })
+                .prop_map(CommitType::Other),
+        ]
+    }
+
+    fn body() -> impl Strategy<Value = String> {
+        let paragraph = prop::collection::vec("[a-zA-Z][a-zA-Z0-9 ,.:#]{0,90}[a-zA-Z0-9.]", 1..4)
+            .prop_map(|lines| lines.join("\n"));
+
+        prop::collection::vec(paragraph, 1..4).prop_map(|paragraphs| paragraphs.join("\n\n"))
+    }
+
+    /// Checks if the last paragraph of the body would be read as footer
+    fn ends_with_footer(body: &str) -> bool {
+        let paragraph = body.rsplit("\n\n").next().unwrap_or_default();
+
+        regex::Regex::new(r"^[A-Za-z][\w-]*(: | #)")
+            .unwrap()
+            .is_match(paragraph)
+    }
+
+    fn footer() -> impl Strategy<Value = (String, String)> {
+        (
+            prop_oneof![
+                Just(String::from("BREAKING CHANGE")),
+                "[A-Z][a-z]{1,8}(-[a-z]{1,8})?"
+            ],
+            "[a-z0-9#][a-z0-9 :#]{0,30}[a-z0-9]",
+        )
+    }
+
+    prop_compose! {
+        fn commit()(
+            commit_type in commit_type(),
+            scopes in prop::collection::vec("[a-z][a-z0-9-]{0,10}", 0..3),
+            breaking in any::<bool>(),
+            description in "[a-zA-Z0-9][a-zA-Z0-9 ,.:#()!]{0,50}[a-zA-Z0-9]",
+            body in prop::option::of(body()),
+            footer in prop::option::of(prop::collection::vec(footer(), 1..4)),
+        ) -> Commit {
+            let breaking = breaking
+                || commit_type == CommitType::BreakingChange
+                || footer.iter().flatten().any(|(t, _)| t == "BREAKING CHANGE");
+
+            Commit { breaking, commit_type, scopes, description, body, footer }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(commit in commit().prop_filter(
+            "a body without footers must not end with a footer",
+            |c| c.footer.is_some() || !c.body.as_deref().is_some_and(ends_with_footer)
+        )) {
+            prop_assert_eq!(Commit::parse(&commit.to_string()), Some(commit));
+        }
+
+        #[test]
+        fn format_is_stable(message in "[a-z]{1,8}(\\([a-z]{1,8}\\))?!?: [a-z][a-z ]{0,40}(\n\n[A-Za-z ]{1,40}){0,2}") {
+            if let Some(commit) = Commit::parse(&message) {
+                let formatted = commit.to_string();
+                prop_assert_eq!(Commit::parse(&formatted).map(|c| c.to_string()), Some(formatted));
+            }
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
+#[cfg(all(test, feature = "serde"))]
+mod serde_test {
 
     use crate::{Commit, CommitType};
 
     #[test]
-    fn commit_type_valid() {
-        let commit_string = "feat: allow provided config object to extend other configs";
+    fn commit_to_json() {
+        let commit = Commit::parse("deps(cli)!: bump clap\n\nRefs: #12").unwrap();
+        let json = serde_json::to_value(&commit).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "breaking": true,
+                "commit_type": "deps",
+                "scopes": ["cli"],
+                "description": "bump clap",
+                "body": null,
+                "footer": [["Refs", "#12"]]
+            })
+        );
+        assert_eq!(serde_json::from_value::<Commit>(json).unwrap(), commit);
+    }
 
-        assert_eq!();
+    #[test]
+    fn commit_type_token() {
+        assert_eq!(
+            serde_json::to_string(&CommitType::Feature).unwrap(),
+            "\"feat\""
+        );
+        assert_eq!(
+            serde_json::from_str::<CommitType>("\"BREAKING CHANGE\"").unwrap(),
+            CommitType::BreakingChange
+        );
+    }
+
+    #[test]
+    fn default_round_trip() {
+        let commit = Commit::default();
+        let json = serde_json::to_string(&commit).unwrap();
+
+        assert_eq!(serde_json::from_str::<Commit>(&json).unwrap(), commit);
+    }
+
+    #[test]
+    fn lint_result_to_json() {
+        let result = Commit::lint("feat: add parser.");
+        let json = serde_json::to_value(&result).unwrap();
+        let lint = &json["lints"][0];
+
+        assert_eq!(lint["level"], "warning");
+        assert_eq!(lint["rule"], "subject-full-stop");
+        assert_eq!(lint["span"]["column"], 17);
+        assert_eq!(lint["fix"]["replacement"], "");
     }
 }
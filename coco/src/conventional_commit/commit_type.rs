@@ -1,4 +1,10 @@
-#[derive(Eq, PartialEq, Debug)]
+use crate::Bump;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The type of a commit, see [CommitType::as_str] for the token of each type in a message
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum CommitType {
     Fix,
     Feature,
@@ -11,11 +17,296 @@ pub enum CommitType {
     Refactor,
     Performance,
     Test,
-    Other,
+    /// Any type that is not defined above, with the type as written in the message
+    Other(String),
+}
+
+impl CommitType {
+    /// The types that are used in the header of a commit message, the most common first
+    ///
+    /// [CommitType::BreakingChange] is left out, breaking changes are marked with `!` or a
+    /// `BREAKING CHANGE` footer instead.
+    pub const CONVENTIONAL: [CommitType; 10] = [
+        CommitType::Feature,
+        CommitType::Fix,
+        CommitType::Docs,
+        CommitType::Style,
+        CommitType::Refactor,
+        CommitType::Performance,
+        CommitType::Test,
+        CommitType::Build,
+        CommitType::Ci,
+        CommitType::Chore,
+    ];
+
+    /// The type as written in a commit message (e.g. `feat` for [CommitType::Feature])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::CommitType;
+    /// assert_eq!(CommitType::Performance.as_str(), "perf");
+    /// assert_eq!(CommitType::Other(String::from("deps")).as_str(), "deps");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            CommitType::Fix => "fix",
+            CommitType::Feature => "feat",
+            CommitType::BreakingChange => "BREAKING CHANGE",
+            CommitType::Build => "build",
+            CommitType::Chore => "chore",
+            CommitType::Style => "style",
+            CommitType::Docs => "docs",
+            CommitType::Refactor => "refactor",
+            CommitType::Performance => "perf",
+            CommitType::Test => "test",
+            CommitType::Ci => "ci",
+            CommitType::Other(t) => t,
+        }
+    }
+
+    /// The heading of the section in a changelog that lists the commits of this type
+    pub fn changelog_heading(&self) -> &'static str {
+        match self {
+            CommitType::Fix => "Bug Fixes",
+            CommitType::Feature => "Features",
+            CommitType::BreakingChange => "Breaking Changes",
+            CommitType::Build => "Build System",
+            CommitType::Chore => "Chores",
+            CommitType::Style => "Styles",
+            CommitType::Docs => "Documentation",
+            CommitType::Refactor => "Code Refactoring",
+            CommitType::Performance => "Performance Improvements",
+            CommitType::Test => "Tests",
+            CommitType::Ci => "Continuous Integration",
+            CommitType::Other(_) => "Other Changes",
+        }
+    }
+
+    /// A short description of the changes a commit of this type contains
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::CommitType;
+    /// assert_eq!(CommitType::Fix.description(), "A bug fix");
+    /// ```
+    pub fn description(&self) -> &'static str {
+        match self {
+            CommitType::Fix => "A bug fix",
+            CommitType::Feature => "A new feature",
+            CommitType::BreakingChange => "A change that is not backwards compatible",
+            CommitType::Build => "Changes that affect the build system or dependencies",
+            CommitType::Chore => "Other changes that do not modify source or test files",
+            CommitType::Style => "Changes that do not affect the meaning of the code",
+            CommitType::Docs => "Documentation only changes",
+            CommitType::Refactor => "A code change that neither fixes a bug nor adds a feature",
+            CommitType::Performance => "A code change that improves performance",
+            CommitType::Test => "Adding missing tests or correcting existing tests",
+            CommitType::Ci => "Changes to the CI configuration files and scripts",
+            CommitType::Other(_) => "Custom type",
+        }
+    }
+
+    /// The level by which a commit of this type increments the version, if it is not marked
+    /// as breaking change
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::{Bump, CommitType};
+    /// assert_eq!(CommitType::Feature.bump(), Bump::Minor);
+    /// assert_eq!(CommitType::Docs.bump(), Bump::None);
+    /// ```
+    pub fn bump(&self) -> Bump {
+        match self {
+            CommitType::Fix => Bump::Patch,
+            CommitType::Feature => Bump::Minor,
+            CommitType::BreakingChange => Bump::Major,
+            _ => Bump::None,
+        }
+    }
+
+    /// Checks if commits of this type are left out of changelogs by default, which are all
+    /// types that do not change the behavior for the user
+    pub fn is_hidden(&self) -> bool {
+        !matches!(
+            self,
+            CommitType::Fix
+                | CommitType::Feature
+                | CommitType::BreakingChange
+                | CommitType::Performance
+        )
+    }
+
+    /// The type of a token in a commit message, the case of the token is ignored
+    pub(crate) fn from_token(token: &str) -> CommitType {
+        match token.to_lowercase().as_str() {
+            "fix" => CommitType::Fix,
+            "feat" => CommitType::Feature,
+            "breaking change" => CommitType::BreakingChange,
+            "build" => CommitType::Build,
+            "chore" => CommitType::Chore,
+            "style" => CommitType::Style,
+            "docs" => CommitType::Docs,
+            "refactor" => CommitType::Refactor,
+            "perf" => CommitType::Performance,
+            "test" => CommitType::Test,
+            "ci" => CommitType::Ci,
+            _ => CommitType::Other(token.to_string()),
+        }
+    }
 }
 
+/// Defaults to `chore`, the type of changes that do not fit any other type
 impl Default for CommitType {
     fn default() -> Self {
-        CommitType::Other
+        CommitType::Chore
+    }
+}
+
+impl fmt::Display for CommitType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The error returned when a string is not a valid commit type
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ParseCommitTypeError {
+    token: String,
+}
+
+impl fmt::Display for ParseCommitTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a valid commit type, types must only contain ascii letters",
+            self.token
+        )
+    }
+}
+
+impl Error for ParseCommitTypeError {}
+
+impl FromStr for CommitType {
+    type Err = ParseCommitTypeError;
+
+    /// Parses a type the same way as the type in the header of a commit message is parsed,
+    /// the case is ignored and unknown types result in [CommitType::Other]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::CommitType;
+    /// assert_eq!("Feat".parse(), Ok(CommitType::Feature));
+    /// assert_eq!("deps".parse(), Ok(CommitType::Other(String::from("deps"))));
+    /// assert!("fix2".parse::<CommitType>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s != "BREAKING CHANGE" && (s.is_empty() || !s.chars().all(|c| c.is_ascii_alphabetic())) {
+            return Err(ParseCommitTypeError {
+                token: s.to_string(),
+            });
+        }
+
+        Ok(CommitType::from_token(s))
+    }
+}
+
+impl TryFrom<&str> for CommitType {
+    type Error = ParseCommitTypeError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for CommitType {
+    type Error = ParseCommitTypeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CommitType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CommitType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod commit_type_test {
+
+    use crate::{Bump, CommitType};
+
+    const KNOWN: &[CommitType] = &[
+        CommitType::Fix,
+        CommitType::Feature,
+        CommitType::BreakingChange,
+        CommitType::Build,
+        CommitType::Chore,
+        CommitType::Ci,
+        CommitType::Docs,
+        CommitType::Style,
+        CommitType::Refactor,
+        CommitType::Performance,
+        CommitType::Test,
+    ];
+
+    #[test]
+    fn token_round_trip() {
+        for commit_type in KNOWN {
+            assert_eq!(commit_type.to_string().parse().as_ref(), Ok(commit_type));
+        }
+    }
+
+    #[test]
+    fn consistent_with_parser() {
+        for commit_type in KNOWN {
+            let commit = crate::Commit::parse(&format!("{}: change", commit_type)).unwrap();
+            assert_eq!(&commit.commit_type, commit_type);
+        }
+    }
+
+    #[test]
+    fn invalid_tokens() {
+        assert!(CommitType::try_from("").is_err());
+        assert!(CommitType::try_from("breaking change").is_err());
+        assert_eq!(
+            CommitType::try_from("fix!").unwrap_err().to_string(),
+            "\"fix!\" is not a valid commit type, types must only contain ascii letters"
+        );
+    }
+
+    #[test]
+    fn metadata() {
+        assert_eq!(CommitType::Fix.changelog_heading(), "Bug Fixes");
+        assert_eq!(CommitType::BreakingChange.bump(), Bump::Major);
+        assert!(CommitType::Chore.is_hidden());
+        assert!(!CommitType::Performance.is_hidden());
+        assert!(CommitType::Other(String::from("deps")).is_hidden());
+        assert_eq!(
+            CommitType::Other(String::from("deps")).description(),
+            "Custom type"
+        );
+    }
+
+    #[test]
+    fn conventional_types() {
+        for commit_type in &CommitType::CONVENTIONAL {
+            assert!(KNOWN.contains(commit_type));
+        }
+        assert!(!CommitType::CONVENTIONAL.contains(&CommitType::BreakingChange));
     }
 }
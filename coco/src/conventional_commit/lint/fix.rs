@@ -0,0 +1,26 @@
+use crate::conventional_commit::lint::Span;
+
+/// A suggested change to the linted message that resolves a violation
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fix {
+    /// The part of the message that is replaced, an empty span inserts the replacement
+    pub span: Span,
+    /// The text that replaces the span
+    pub replacement: String,
+}
+
+impl Fix {
+    /// Creates a fix that replaces the span with the given text
+    pub fn new(span: Span, replacement: &str) -> Fix {
+        Fix {
+            span,
+            replacement: String::from(replacement),
+        }
+    }
+
+    /// Creates a fix that removes the span from the message
+    pub fn remove(span: Span) -> Fix {
+        Fix::new(span, "")
+    }
+}
@@ -1,6 +1,11 @@
 use clap::ValueEnum;
 use std::fmt;
 #[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy, ValueEnum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Level {
     Error,
     Warning,
@@ -1,6 +1,157 @@
-use super::Violation;
+use super::{Fix, Level, Violation};
 use crate::Commit;
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LintResult {
     pub commit: Option<Commit>,
     pub lints: Vec<Violation>,
+    /// Violations of rules that are ignored by a `Cocors-Ignore` footer in the message
+    pub ignored: Vec<Violation>,
+}
+
+impl LintResult {
+    /// Checks if the message is valid, which is the case if no violation is an error
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Commit;
+    /// assert!(Commit::lint("feat: add parser").is_valid());
+    /// assert!(!Commit::lint("feat:add parser").is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        !self.lints.iter().any(|l| l.level == Level::Error)
+    }
+
+    /// Suggested fixes of all violations, ordered by their position in the message
+    pub fn fixes(&self) -> Vec<&Fix> {
+        let mut fixes: Vec<&Fix> = self.lints.iter().filter_map(|l| l.fix.as_ref()).collect();
+        fixes.sort_by_key(|f| (f.span.start, f.span.end));
+        fixes
+    }
+
+    /// Applies all suggested fixes to the linted message and returns the corrected message
+    ///
+    /// `message` must be the message that was linted. If fixes overlap only the first one is
+    /// applied, linting the corrected message again shows the remaining violations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Commit;
+    /// let message = "Feat:add parser.\nSupports arrays";
+    /// let result = Commit::lint(message);
+    ///
+    /// assert_eq!(result.fix(message), "feat: add parser\n\nSupports arrays");
+    /// ```
+    pub fn fix(&self, message: &str) -> String {
+        let mut fixed = String::with_capacity(message.len());
+        let mut position = 0;
+
+        for fix in self.fixes() {
+            if fix.span.start < position {
+                continue;
+            }
+
+            fixed.push_str(&message[position..fix.span.start]);
+            fixed.push_str(&fix.replacement);
+            position = fix.span.end;
+        }
+
+        fixed.push_str(&message[position..]);
+        fixed
+    }
+}
+
+#[cfg(test)]
+mod fix_test {
+
+    use crate::lint::{Case, Level, Rule, Rules};
+    use crate::Commit;
+
+    fn fix(message: &str, rules: &Rules) -> String {
+        Commit::lint_with(message, rules).fix(message)
+    }
+
+    #[test]
+    fn fix_header() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            fix("FEAT(API):add parser. ", &rules),
+            "feat(api): add parser"
+        );
+        assert_eq!(fix("fix:  remove typo", &rules), "fix: remove typo");
+    }
+
+    #[test]
+    fn fix_subject_case() {
+        let rules = Rules {
+            subject_case: Some(Rule::new(Level::Warning, Case::Sentence)),
+            ..Rules::default()
+        };
+
+        assert_eq!(fix("fix: remove typo", &rules), "fix: Remove typo");
+    }
+
+    #[test]
+    fn fix_body_and_footer() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            fix("fix: remove typo\r\nThe typo was in the readme", &rules),
+            "fix: remove typo\r\n\r\nThe typo was in the readme"
+        );
+        assert_eq!(
+            fix(
+                "fix: remove typo\n\nThe typo.\n\nBreaking-Change: typo\nReviewed by: Z",
+                &rules
+            ),
+            "fix: remove typo\n\nThe typo.\n\nBREAKING CHANGE: typo\nReviewed-by: Z"
+        );
+    }
+
+    #[test]
+    fn fix_valid_message() {
+        let message = "fix: remove typo\n\nThe typo was in the readme\n\nRefs: #12";
+
+        assert_eq!(fix(message, &Rules::default()), message);
+    }
+
+    #[test]
+    fn unfixable() {
+        let result = Commit::lint("feat add parser");
+
+        assert!(result.fixes().is_empty());
+        assert_eq!(result.fix("feat add parser"), "feat add parser");
+    }
+}
+
+#[cfg(test)]
+mod valid_test {
+
+    use crate::lint::{Level, LintResult, Violation};
+
+    fn violation(level: Level) -> Violation {
+        Violation {
+            level,
+            rule: String::from("test"),
+            message: String::from("Something happened"),
+            description: None,
+            span: None,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn only_errors_are_invalid() {
+        let mut result = LintResult {
+            commit: None,
+            lints: vec![violation(Level::Warning), violation(Level::Suggestion)],
+            ignored: vec![violation(Level::Error)],
+        };
+        assert!(result.is_valid());
+
+        result.lints.push(violation(Level::Error));
+        assert!(!result.is_valid());
+    }
 }
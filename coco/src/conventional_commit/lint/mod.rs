@@ -1,7 +1,13 @@
+mod fix;
 mod level;
 mod lint_result;
+pub(crate) mod rules;
+mod span;
 mod violation;
 
+pub use fix::Fix;
 pub use level::Level;
 pub use lint_result::LintResult;
+pub use rules::{Case, Pattern, Rule, Rules};
+pub use span::{Part, Span};
 pub use violation::Violation;
@@ -0,0 +1,359 @@
+use super::Rules;
+use crate::conventional_commit::lint::{Fix, Level, LintResult, Part, Violation};
+use crate::parser::Message;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    /// Matches the start of a footer with a token that does not contain whitespace
+    static ref FOOTER_START_RE: Regex = Regex::new(r"^(BREAKING CHANGE|[A-Za-z][\w-]*)(: | #)").unwrap();
+    /// Matches the start of a footer with a token that may contain a single whitespace, to
+    /// report tokens like `Reviewed by` that the parser keeps in the body
+    static ref SPACED_FOOTER_RE: Regex =
+        Regex::new(r"^([A-Za-z][\w-]*(?: [A-Za-z][\w-]*)?)(: | #)").unwrap();
+    static ref FOOTER_TOKEN_RE: Regex =
+        Regex::new(r"^(BREAKING CHANGE|[A-Za-z][A-Za-z0-9]*(-[A-Za-z0-9]+)*)$").unwrap();
+}
+
+/// Runs all body and footer rules against the parsed commit message
+pub(crate) fn lint(result: &mut LintResult, rules: &Rules, message: &str, parsed: &Message) {
+    let body = parsed.body.map_or_else(Vec::new, |b| b.lines());
+
+    if let Some(level) = rules.body_leading_blank {
+        let lines = Part::new(0, message).lines();
+
+        if let Some(line) = lines.get(1).filter(|l| !l.text.trim().is_empty()) {
+            result.lints.push(Violation {
+                level,
+                rule: String::from("body-leading-blank"),
+                message: String::from("Body must be separated from the header by a blank line"),
+                description: None,
+                span: Some(line.full_span(message)),
+                fix: Some(Fix::new(line.span(message, 0, 0), line_ending(message))),
+            });
+        }
+    }
+
+    let last_paragraph = body
+        .iter()
+        .rposition(|l| l.text.trim().is_empty())
+        .map_or(&body[..], |i| &body[i + 1..]);
+
+    if let Some(level) = rules.footer_leading_blank {
+        if let Some(line) = last_paragraph
+            .iter()
+            .skip(1)
+            .find(|l| FOOTER_START_RE.is_match(l.text))
+        {
+            result.lints.push(Violation {
+                level,
+                rule: String::from("footer-leading-blank"),
+                message: String::from("Footer must be separated from the body by a blank line"),
+                description: None,
+                span: Some(line.full_span(message)),
+                fix: Some(Fix::new(line.span(message, 0, 0), line_ending(message))),
+            });
+        }
+    }
+
+    if let Some(rule) = &rules.body_max_line_length {
+        for line in &body {
+            let length = line.text.chars().count();
+            if length > rule.value && !line.text.contains("://") {
+                let from = line
+                    .text
+                    .char_indices()
+                    .nth(rule.value)
+                    .map_or(line.text.len(), |(i, _)| i);
+
+                result.lints.push(Violation {
+                    level: rule.level,
+                    rule: String::from("body-max-line-length"),
+                    message: format!(
+                        "Body line is {} characters long, the maximum is {}",
+                        length, rule.value
+                    ),
+                    description: Some(String::from("Wrap the line")),
+                    span: Some(line.span(message, from, line.text.len())),
+                    fix: None,
+                });
+            }
+        }
+    }
+
+    if parsed.body.is_none() {
+        let commit_type = parsed.header.commit_type.text.to_lowercase();
+
+        if let Some(rule) = &rules.body_required {
+            if rule.value.iter().any(|t| t.to_lowercase() == commit_type) {
+                push_body_required(result, rule.level, format!("type \"{}\"", commit_type));
+            }
+        }
+
+        if let Some(level) = rules.body_required_breaking {
+            if parsed.is_breaking() {
+                push_body_required(result, level, String::from("breaking changes"));
+            }
+        }
+    }
+
+    if let Some(level) = rules.footer_token_format {
+        // Tokens with whitespace do not start a footer, so they are searched in the lines of
+        // the footers and in a last paragraph of the body that only consists of footers
+        let lines = match (parsed.footers.first(), parsed.footers.last()) {
+            (Some(first), Some(last)) => Part::new(
+                first.token.start,
+                &message[first.token.start..last.value.end()],
+            )
+            .lines(),
+            _ if last_paragraph
+                .iter()
+                .all(|l| SPACED_FOOTER_RE.is_match(l.text)) =>
+            {
+                last_paragraph.to_vec()
+            }
+            _ => Vec::new(),
+        };
+        let tokens = lines.iter().filter_map(|l| {
+            let token = SPACED_FOOTER_RE.captures(l.text)?.get(1)?;
+            Some(Part::new(l.start, token.as_str()))
+        });
+
+        for token in tokens {
+            if is_misspelled_breaking_change(token.text) {
+                result.lints.push(Violation {
+                    level,
+                    rule: String::from("footer-token-format"),
+                    message: format!("Footer token \"{}\" is not valid", token.text),
+                    description: Some(String::from(
+                        "Breaking changes must be marked with an uppercase BREAKING CHANGE footer",
+                    )),
+                    span: Some(token.full_span(message)),
+                    fix: Some(Fix::new(token.full_span(message), "BREAKING CHANGE")),
+                });
+            } else if !FOOTER_TOKEN_RE.is_match(token.text) {
+                let replacement = token.text.replace([' ', '_'], "-");
+
+                result.lints.push(Violation {
+                    level,
+                    rule: String::from("footer-token-format"),
+                    message: format!("Footer token \"{}\" is not valid", token.text),
+                    description: Some(format!(
+                        "Footer tokens must use `-` in place of whitespace (e.g. \"{}\"), only BREAKING CHANGE may contain a space",
+                        replacement
+                    )),
+                    span: Some(token.full_span(message)),
+                    fix: FOOTER_TOKEN_RE
+                        .is_match(&replacement)
+                        .then(|| Fix::new(token.full_span(message), &replacement)),
+                });
+            }
+        }
+    }
+
+    if let Some(rule) = &rules.footer_references {
+        if !parsed
+            .footers
+            .iter()
+            .any(|f| rule.value.is_match(&message[f.token.start..f.value.end()]))
+        {
+            result.lints.push(Violation {
+                level: rule.level,
+                rule: String::from("footer-references"),
+                message: String::from("Reference footer is missing"),
+                description: Some(format!(
+                    "Add a footer that references an issue and matches \"{}\"",
+                    rule.value
+                )),
+                span: None,
+                fix: None,
+            });
+        }
+    }
+}
+
+/// Checks if the token is meant to be a BREAKING CHANGE footer but not written as specified
+/// (e.g. `Breaking Change` or `BREAKING_CHANGE`)
+fn is_misspelled_breaking_change(token: &str) -> bool {
+    token != "BREAKING CHANGE"
+        && token != "BREAKING-CHANGE"
+        && token.to_uppercase().replace(['-', '_'], " ") == "BREAKING CHANGE"
+}
+
+/// The line ending used in the message, `\r\n` if the message contains one
+fn line_ending(message: &str) -> &'static str {
+    if message.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn push_body_required(result: &mut LintResult, level: Level, reason: String) {
+    result.lints.push(Violation {
+        level,
+        rule: String::from("body-required"),
+        message: format!("Body is required for {}", reason),
+        description: Some(String::from(
+            "Describe the motivation and the impact of the change in the body of the commit message",
+        )),
+        span: None,
+        fix: None,
+    });
+}
+
+#[cfg(test)]
+mod body_test {
+
+    use crate::lint::{Level, Pattern, Rule, Rules};
+    use crate::Commit;
+
+    fn rules_violated(commit: &str, rules: &Rules) -> Vec<String> {
+        Commit::lint_with(commit, rules)
+            .lints
+            .into_iter()
+            .map(|l| l.rule)
+            .filter(|r| r.starts_with("body") || r.starts_with("footer"))
+            .collect()
+    }
+
+    #[test]
+    fn body_and_footer() {
+        let commit = Commit::parse(
+            "fix: prevent racing of requests\n\nIntroduce a request id.\n\nRemove timeouts.\n\nReviewed-by: Z\nRefs #123\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            commit.body.as_deref(),
+            Some("Introduce a request id.\n\nRemove timeouts.")
+        );
+
+        assert_eq!(
+            commit.footer.unwrap(),
+            vec![
+                (String::from("Reviewed-by"), String::from("Z")),
+                (String::from("Refs"), String::from("#123"))
+            ]
+        );
+    }
+
+    #[test]
+    fn breaking_change_footer() {
+        let commit =
+            Commit::parse("feat: allow config\n\nBREAKING CHANGE: `extends` key is used\n")
+                .unwrap();
+
+        assert!(commit.breaking);
+        assert!(commit.body.is_none());
+    }
+
+    #[test]
+    fn body_leading_blank() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            rules_violated("fix: remove typo\nThe typo was in the readme", &rules),
+            vec!["body-leading-blank", "footer-missing"]
+        );
+    }
+
+    #[test]
+    fn footer_leading_blank() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            rules_violated(
+                "fix: remove typo\n\nThe typo was in the readme\nRefs: #12",
+                &rules
+            ),
+            vec!["footer-leading-blank", "footer-missing"]
+        );
+        assert!(rules_violated("fix: remove typo\n\nThe typo.\n\nRefs: #12", &rules).is_empty());
+    }
+
+    #[test]
+    fn body_max_line_length() {
+        let rules = Rules {
+            body_max_line_length: Some(Rule::new(Level::Warning, 20)),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated(
+                "fix: remove typo\n\nThe typo was in the readme\n\nRefs: #12",
+                &rules
+            ),
+            vec!["body-max-line-length"]
+        );
+        assert!(rules_violated(
+            "fix: remove typo\n\nhttps://github.com/felivora/cocors/issues/1\n\nRefs: #12",
+            &rules
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn body_required() {
+        let rules = Rules {
+            body_required: Some(Rule::new(Level::Error, vec![String::from("feat")])),
+            body_required_breaking: Some(Level::Error),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("feat: add parser", &rules),
+            vec!["body-required"]
+        );
+        assert_eq!(
+            rules_violated("fix!: remove parser", &rules),
+            vec!["body-required"]
+        );
+        assert!(rules_violated("fix: remove typo", &rules).is_empty());
+        assert!(Commit::lint_with("feat: add parser", &rules)
+            .commit
+            .is_none());
+    }
+
+    #[test]
+    fn footer_token_format() {
+        let rules = Rules::default();
+
+        // the invalid token is not parsed as footer, so the footer is missing as well
+        assert_eq!(
+            rules_violated("fix: remove typo\n\nReviewed by: Z", &rules),
+            vec!["footer-token-format", "footer-missing"]
+        );
+        assert_eq!(
+            rules_violated("fix: remove typo\n\nRefs: #1\nReviewed by: Z", &rules),
+            vec!["footer-token-format"]
+        );
+        assert!(rules_violated("fix: remove typo\n\nBREAKING CHANGE: typo", &rules).is_empty());
+        assert!(
+            rules_violated("fix: remove typo\n\nBreaking change: typo", &rules)
+                .contains(&String::from("footer-token-format"))
+        );
+        assert!(!rules_violated(
+            "fix: remove typo\n\nNote that: the typo\nwas in the readme",
+            &rules
+        )
+        .contains(&String::from("footer-token-format")));
+    }
+
+    #[test]
+    fn footer_references() {
+        let rules = Rules {
+            footer_references: Some(Rule::new(
+                Level::Error,
+                Pattern::new(r"^(Refs|Closes):? #\d+").unwrap(),
+            )),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("fix: remove typo\n\nReviewed-by: Z", &rules),
+            vec!["footer-references"]
+        );
+        assert!(rules_violated("fix: remove typo\n\nCloses #12", &rules).is_empty());
+    }
+}
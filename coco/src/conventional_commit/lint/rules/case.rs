@@ -0,0 +1,81 @@
+use clap::ValueEnum;
+use std::fmt;
+
+/// The case a text (e.g. the description of a commit) must be written in
+#[derive(PartialEq, Eq, Debug, Clone, Copy, ValueEnum)]
+pub enum Case {
+    /// Must not start with an uppercase letter (e.g. `add parser`)
+    Lower,
+    /// Must start with an uppercase letter (e.g. `Add parser`)
+    Sentence,
+    /// Every word must start with an uppercase letter (e.g. `Add Parser`)
+    Start,
+    /// Must not contain any lowercase letters (e.g. `ADD PARSER`)
+    Upper,
+}
+
+impl Case {
+    /// Checks if the given text is written in this case
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::lint::Case;
+    /// assert!(Case::Lower.matches("add the API parser"));
+    /// assert!(!Case::Sentence.matches("add the API parser"));
+    /// assert!(Case::Start.matches("Add The API Parser"));
+    /// ```
+    pub fn matches(&self, text: &str) -> bool {
+        let starts_upper = |w: &str| w.chars().next().is_none_or(|c| c.is_uppercase());
+
+        match self {
+            Case::Lower => !text.chars().next().is_some_and(|c| c.is_uppercase()),
+            Case::Sentence => starts_upper(text),
+            Case::Start => text.split_whitespace().all(starts_upper),
+            Case::Upper => !text.chars().any(|c| c.is_lowercase()),
+        }
+    }
+
+    /// Converts the text into this case, only the letters that are checked by
+    /// [matches](Case::matches) are changed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::lint::Case;
+    /// assert_eq!(Case::Lower.convert("Add the API parser"), "add the API parser");
+    /// assert_eq!(Case::Start.convert("add the API parser"), "Add The API Parser");
+    /// ```
+    pub fn convert(&self, text: &str) -> String {
+        let capitalize = |w: &str, upper: bool| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(c) if upper => c.to_uppercase().chain(chars).collect(),
+                Some(c) => c.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        };
+
+        match self {
+            Case::Lower => capitalize(text, false),
+            Case::Sentence => capitalize(text, true),
+            Case::Start => text
+                .split(' ')
+                .map(|w| capitalize(w, true))
+                .collect::<Vec<String>>()
+                .join(" "),
+            Case::Upper => text.to_uppercase(),
+        }
+    }
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Case::Lower => write!(f, "lower case"),
+            Case::Sentence => write!(f, "sentence case"),
+            Case::Start => write!(f, "start case"),
+            Case::Upper => write!(f, "upper case"),
+        }
+    }
+}
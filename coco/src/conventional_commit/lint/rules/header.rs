@@ -0,0 +1,346 @@
+use super::Rules;
+use crate::conventional_commit::lint::{Fix, LintResult, Part, Violation};
+use crate::parser::Header;
+
+/// Verbs that are commonly used at the start of a commit description, used to suggest the
+/// imperative form of a word that is written in past tense or third person
+const COMMON_VERBS: &[&str] = &[
+    "add",
+    "allow",
+    "apply",
+    "bump",
+    "change",
+    "clean",
+    "copy",
+    "create",
+    "delete",
+    "deprecate",
+    "disable",
+    "drop",
+    "enable",
+    "ensure",
+    "extend",
+    "fix",
+    "handle",
+    "implement",
+    "improve",
+    "introduce",
+    "merge",
+    "move",
+    "prevent",
+    "refactor",
+    "release",
+    "remove",
+    "rename",
+    "replace",
+    "resolve",
+    "revert",
+    "simplify",
+    "support",
+    "update",
+    "upgrade",
+    "use",
+];
+
+/// Words ending in `ed` that are already in imperative mood
+const IMPERATIVE_ED: &[&str] = &[
+    "embed", "exceed", "feed", "need", "proceed", "seed", "shed", "speed", "succeed",
+];
+
+/// Runs all type, header and subject rules against the header of a commit
+pub(crate) fn lint(result: &mut LintResult, rules: &Rules, message: &str, parsed: &Header) {
+    let header = parsed.line;
+    let description = parsed.description;
+
+    if let Some(rule) = &rules.type_case {
+        let commit_type = parsed.commit_type;
+
+        if commit_type.text != "BREAKING CHANGE" && !rule.value.matches(commit_type.text) {
+            result.lints.push(Violation {
+                level: rule.level,
+                rule: String::from("type-case"),
+                message: format!("Type must be written in {}", rule.value),
+                description: None,
+                span: Some(commit_type.full_span(message)),
+                fix: Some(Fix::new(
+                    commit_type.full_span(message),
+                    &rule.value.convert(&commit_type.text.to_lowercase()),
+                )),
+            });
+        }
+    }
+
+    if let Some(rule) = &rules.header_max_length {
+        let length = header.text.chars().count();
+        if length > rule.value {
+            let from = header
+                .text
+                .char_indices()
+                .nth(rule.value)
+                .map_or(header.text.len(), |(i, _)| i);
+
+            result.lints.push(Violation {
+                level: rule.level,
+                rule: String::from("header-max-length"),
+                message: format!(
+                    "Header is {} characters long, the maximum is {}",
+                    length, rule.value
+                ),
+                description: Some(String::from(
+                    "Keep the header short and move details into the body of the commit message",
+                )),
+                span: Some(header.span(message, from, header.text.len())),
+                fix: None,
+            });
+        }
+    }
+
+    if let Some(level) = rules.header_trim {
+        let leading = description.text.len() - description.text.trim_start().len();
+        let trailing = header.text.trim_end().len();
+
+        let span = if leading > 0 {
+            Some(description.span(message, 0, leading))
+        } else if trailing < header.text.len() {
+            Some(header.span(message, trailing, header.text.len()))
+        } else {
+            None
+        };
+
+        if span.is_some() {
+            result.lints.push(Violation {
+                level,
+                rule: String::from("header-trim"),
+                message: String::from("Header contains leading or trailing whitespace"),
+                description: Some(String::from(
+                    "Remove the whitespace around the header and use exactly one space after the colon",
+                )),
+                span,
+                fix: span.map(Fix::remove),
+            });
+        }
+    }
+
+    let leading = description.text.len() - description.text.trim_start().len();
+    let description = Part::new(description.start + leading, description.text.trim());
+
+    if let Some(rule) = &rules.subject_case {
+        if !rule.value.matches(description.text) {
+            result.lints.push(Violation {
+                level: rule.level,
+                rule: String::from("subject-case"),
+                message: format!("Description must be written in {}", rule.value),
+                description: None,
+                span: Some(description.full_span(message)),
+                fix: Some(Fix::new(
+                    description.full_span(message),
+                    &rule.value.convert(description.text),
+                )),
+            });
+        }
+    }
+
+    if let Some(level) = rules.subject_full_stop {
+        if description.text.ends_with('.') {
+            let end = description.text.len();
+            let span = description.span(message, end - 1, end);
+
+            result.lints.push(Violation {
+                level,
+                rule: String::from("subject-full-stop"),
+                message: String::from("Description must not end with a period"),
+                description: None,
+                span: Some(span),
+                fix: Some(Fix::remove(span)),
+            });
+        }
+    }
+
+    if let Some(level) = rules.subject_imperative {
+        let first = description
+            .text
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+        let word = first.to_lowercase();
+
+        if let Some(suggestion) = imperative_suggestion(&word) {
+            result.lints.push(Violation {
+                level,
+                rule: String::from("subject-imperative"),
+                message: String::from("Description should be written in imperative mood"),
+                description: Some(match suggestion {
+                    Some(s) => format!("Use \"{}\" instead of \"{}\"", s, word),
+                    None => format!("\"{}\" looks like past tense, describe what the commit does (e.g. \"add\" instead of \"added\")", word),
+                }),
+                span: Some(description.span(message, 0, first.len())),
+                fix: None,
+            });
+        }
+    }
+}
+
+/// Checks if the (lowercase) word is not in imperative mood
+///
+/// Returns `None` if the word looks fine, otherwise the imperative form of the word if it
+/// could be determined.
+fn imperative_suggestion(word: &str) -> Option<Option<&'static str>> {
+    if COMMON_VERBS.contains(&word) {
+        return None;
+    }
+
+    for verb in COMMON_VERBS {
+        let stem = verb.strip_suffix('e').unwrap_or(verb);
+        let y_stem = verb.strip_suffix('y').map(|s| format!("{}i", s));
+        let forms = [
+            format!("{}ed", verb),
+            format!("{}d", verb),
+            format!("{}s", verb),
+            format!("{}es", verb),
+            format!("{}ing", stem),
+        ];
+
+        if forms.iter().any(|f| f == word)
+            || y_stem.is_some_and(|s| word == format!("{}ed", s) || word == format!("{}es", s))
+        {
+            return Some(Some(verb));
+        }
+    }
+
+    if word.len() > 4 && word.ends_with("ed") && !IMPERATIVE_ED.contains(&word) {
+        return Some(None);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod header_test {
+
+    use crate::lint::{Case, Level, Rule, Rules};
+    use crate::Commit;
+
+    fn rules_violated(commit: &str, rules: &Rules) -> Vec<String> {
+        Commit::lint_with(commit, rules)
+            .lints
+            .into_iter()
+            .map(|l| l.rule)
+            .filter(|r| r.starts_with("header") || r.starts_with("subject"))
+            .collect()
+    }
+
+    #[test]
+    fn valid_header() {
+        let rules = Rules::default();
+
+        assert!(rules_violated("feat(parser): add the ability to parse arrays", &rules).is_empty());
+    }
+
+    #[test]
+    fn header_max_length() {
+        let rules = Rules {
+            header_max_length: Some(Rule::new(Level::Error, 20)),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("feat: add the ability to parse arrays", &rules),
+            vec!["header-max-length"]
+        );
+        assert!(rules_violated("feat: add arrays", &rules).is_empty());
+    }
+
+    #[test]
+    fn header_trim() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            rules_violated("feat:  add arrays", &rules),
+            vec!["header-trim"]
+        );
+        assert_eq!(
+            rules_violated("feat: add arrays ", &rules),
+            vec!["header-trim"]
+        );
+    }
+
+    #[test]
+    fn subject_case() {
+        let rules = Rules {
+            subject_case: Some(Rule::new(Level::Error, Case::Lower)),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("feat: Add arrays", &rules),
+            vec!["subject-case"]
+        );
+        assert!(rules_violated("feat: add API arrays", &rules).is_empty());
+
+        let rules = Rules {
+            subject_case: Some(Rule::new(Level::Error, Case::Upper)),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("feat: Add arrays", &rules),
+            vec!["subject-case"]
+        );
+        assert!(rules_violated("feat: ADD ARRAYS", &rules).is_empty());
+    }
+
+    #[test]
+    fn subject_full_stop() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            rules_violated("fix: remove the typo.", &rules),
+            vec!["subject-full-stop"]
+        );
+    }
+
+    #[test]
+    fn subject_imperative() {
+        let rules = Rules::default();
+
+        let result = Commit::lint_with("feat: added arrays", &rules);
+        let lint = result
+            .lints
+            .iter()
+            .find(|l| l.rule == "subject-imperative")
+            .unwrap();
+
+        assert_eq!(
+            lint.description.as_deref(),
+            Some("Use \"add\" instead of \"added\"")
+        );
+        assert_eq!(
+            rules_violated("fix: copies the right files", &rules),
+            vec!["subject-imperative"]
+        );
+        assert_eq!(
+            rules_violated("fix: documented the flags", &rules),
+            vec!["subject-imperative"]
+        );
+        assert!(rules_violated("fix: embed the files", &rules).is_empty());
+        assert!(rules_violated("fix: bring back the flags", &rules).is_empty());
+    }
+
+    #[test]
+    fn disabled_rules() {
+        let rules = Rules {
+            header_max_length: None,
+            header_trim: None,
+            subject_case: None,
+            subject_full_stop: None,
+            subject_imperative: None,
+            ..Rules::default()
+        };
+
+        assert!(rules_violated(
+            "feat: Added a very long description that goes on and on and on and on and on.",
+            &rules
+        )
+        .is_empty());
+    }
+}
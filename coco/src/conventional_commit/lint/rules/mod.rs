@@ -0,0 +1,150 @@
+pub(crate) mod body;
+mod case;
+pub(crate) mod header;
+pub(crate) mod scope;
+
+use std::fmt;
+
+use regex::Regex;
+
+use super::Level;
+
+pub use case::Case;
+
+/// Configuration of a lint rule that takes a value, e.g. the maximum length of the header
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Rule<T> {
+    /// The level at which a violation of the rule is reported
+    pub level: Level,
+    /// The value the rule is checked against
+    pub value: T,
+}
+
+impl<T> Rule<T> {
+    /// Creates a new rule reported at `level` and checked against `value`
+    pub fn new(level: Level, value: T) -> Self {
+        Rule { level, value }
+    }
+}
+
+/// A regex that is compiled once when the rules are configured
+///
+/// # Examples
+///
+/// ```rust
+/// # use coco::lint::Pattern;
+/// let pattern = Pattern::new(r"^Refs: #\d+").unwrap();
+/// assert!(pattern.is_match("Refs: #12"));
+/// assert!(Pattern::new("(").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// Compiles the pattern, returns an error if it is not a valid regex
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Pattern)
+    }
+
+    /// Checks if the pattern matches anywhere in `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+
+    /// The pattern the regex was compiled from
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The set of rules that are checked when linting a commit message
+///
+/// Every rule is optional, a rule set to `None` is disabled and will not report any violations.
+/// Rules that do not take a value are only configured by the [Level] they are reported at.
+///
+/// # Examples
+///
+/// ```rust
+/// # use coco::Commit;
+/// # use coco::lint::{Case, Level, Rule, Rules};
+/// let rules = Rules {
+///     header_max_length: Some(Rule::new(Level::Error, 50)),
+///     subject_case: Some(Rule::new(Level::Error, Case::Lower)),
+///     ..Rules::default()
+/// };
+///
+/// let result = Commit::lint_with("feat: Add the parser", &rules);
+/// assert!(result.commit.is_none());
+/// ```
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Rules {
+    /// `type-case`: the case the type must be written in
+    pub type_case: Option<Rule<Case>>,
+    /// `header-max-length`: maximum amount of characters in the header line
+    pub header_max_length: Option<Rule<usize>>,
+    /// `header-trim`: the header must not have leading or trailing whitespace
+    pub header_trim: Option<Level>,
+    /// `subject-case`: the case the description must be written in
+    pub subject_case: Option<Rule<Case>>,
+    /// `subject-full-stop`: the description must not end with a period
+    pub subject_full_stop: Option<Level>,
+    /// `subject-imperative`: the description should start with a verb in imperative mood
+    /// (e.g. `add` instead of `added`), this is only a heuristic
+    pub subject_imperative: Option<Level>,
+    /// `body-leading-blank`: the body must be separated from the header by a blank line
+    pub body_leading_blank: Option<Level>,
+    /// `footer-leading-blank`: the footer must be separated from the body by a blank line
+    pub footer_leading_blank: Option<Level>,
+    /// `body-max-line-length`: maximum amount of characters in a line of the body, lines
+    /// containing an URL are exempt
+    pub body_max_line_length: Option<Rule<usize>>,
+    /// `body-required`: the commit types (e.g. `feat`) that require a body
+    pub body_required: Option<Rule<Vec<String>>>,
+    /// `body-required-breaking`: breaking changes require a body
+    pub body_required_breaking: Option<Level>,
+    /// `footer-token-format`: footer tokens must use `-` instead of whitespace (e.g. `Reviewed-by`),
+    /// `BREAKING CHANGE` is the only exception
+    pub footer_token_format: Option<Level>,
+    /// `footer-references`: at least one footer must match the regex (e.g. `^Refs: #\d+`)
+    pub footer_references: Option<Rule<Pattern>>,
+    /// `scope-enum`: the scopes that are allowed to be used
+    pub scope_enum: Option<Rule<Vec<String>>>,
+    /// `scope-case`: the case every scope must be written in
+    pub scope_case: Option<Rule<Case>>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            type_case: Some(Rule::new(Level::Warning, Case::Lower)),
+            header_max_length: Some(Rule::new(Level::Warning, 72)),
+            header_trim: Some(Level::Warning),
+            subject_case: None,
+            subject_full_stop: Some(Level::Warning),
+            subject_imperative: Some(Level::Suggestion),
+            body_leading_blank: Some(Level::Warning),
+            footer_leading_blank: Some(Level::Warning),
+            body_max_line_length: Some(Rule::new(Level::Warning, 100)),
+            body_required: None,
+            body_required_breaking: None,
+            footer_token_format: Some(Level::Warning),
+            footer_references: None,
+            scope_enum: None,
+            scope_case: Some(Rule::new(Level::Warning, Case::Lower)),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use super::Rules;
+use crate::conventional_commit::lint::{Fix, LintResult, Part, Violation};
+
+/// Runs all scope rules against the scopes of a commit
+pub(crate) fn lint(result: &mut LintResult, rules: &Rules, message: &str, scopes: &[Part]) {
+    if let Some(rule) = &rules.scope_enum {
+        for scope in scopes.iter().filter(|s| !s.text.is_empty()) {
+            if !rule.value.iter().any(|s| s == scope.text) {
+                result.lints.push(Violation {
+                    level: rule.level,
+                    rule: String::from("scope-enum"),
+                    message: format!("Scope \"{}\" is not allowed", scope.text),
+                    description: Some(format!("Allowed scopes are: {}", rule.value.join(", "))),
+                    span: Some(scope.full_span(message)),
+                    fix: None,
+                });
+            }
+        }
+    }
+
+    // Empty scopes are reported by the parser as scope-empty, they have no case to check
+    if let Some(rule) = &rules.scope_case {
+        for scope in scopes
+            .iter()
+            .filter(|s| !s.text.is_empty() && !rule.value.matches(s.text))
+        {
+            result.lints.push(Violation {
+                level: rule.level,
+                rule: String::from("scope-case"),
+                message: format!("Scope \"{}\" must be written in {}", scope.text, rule.value),
+                description: None,
+                span: Some(scope.full_span(message)),
+                fix: Some(Fix::new(
+                    scope.full_span(message),
+                    &rule.value.convert(&scope.text.to_lowercase()),
+                )),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod scope_test {
+
+    use crate::lint::{Case, Level, Rule, Rules};
+    use crate::Commit;
+
+    fn rules_violated(commit: &str, rules: &Rules) -> Vec<String> {
+        Commit::lint_with(commit, rules)
+            .lints
+            .into_iter()
+            .map(|l| l.rule)
+            .filter(|r| r.starts_with("scope"))
+            .collect()
+    }
+
+    #[test]
+    fn multiple_scopes() {
+        let commit = Commit::parse("feat(api, cli): add version command").unwrap();
+
+        assert_eq!(commit.scopes, vec!["api", "cli"]);
+        assert!(Commit::parse("feat(api,): add version command").is_none());
+    }
+
+    #[test]
+    fn scope_enum() {
+        let rules = Rules {
+            scope_enum: Some(Rule::new(
+                Level::Error,
+                vec![String::from("api"), String::from("cli")],
+            )),
+            ..Rules::default()
+        };
+
+        assert!(rules_violated("feat(api,cli): add version command", &rules).is_empty());
+        assert_eq!(
+            rules_violated("feat(api,parser): add version command", &rules),
+            vec!["scope-enum"]
+        );
+        assert!(Commit::lint_with("feat(parser): add version", &rules)
+            .commit
+            .is_none());
+    }
+
+    #[test]
+    fn scope_case() {
+        let rules = Rules {
+            scope_case: Some(Rule::new(Level::Warning, Case::Lower)),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("feat(api,Cli): add version command", &rules),
+            vec!["scope-case"]
+        );
+    }
+
+    #[test]
+    fn scope_case_skips_empty_scope() {
+        let rules = Rules {
+            scope_case: Some(Rule::new(Level::Warning, Case::Upper)),
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules_violated("feat(API,): add version command", &rules),
+            vec!["scope-empty"]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+/// The location of a violation in the linted commit message
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    /// Byte offset of the start of the span in the message
+    pub start: usize,
+    /// Byte offset of the end of the span (exclusive) in the message
+    pub end: usize,
+    /// Line of the start of the span, starting at 1
+    pub line: usize,
+    /// Column (in characters) of the start of the span, starting at 1
+    pub column: usize,
+}
+
+impl Span {
+    /// Creates the span of the byte range `start..end` in the message and determines its
+    /// line and column
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::lint::Span;
+    /// let span = Span::new("feat: add parser\n\nSupports äöü.", 27, 33);
+    ///
+    /// assert_eq!(span.line, 3);
+    /// assert_eq!(span.column, 10);
+    /// ```
+    pub fn new(message: &str, start: usize, end: usize) -> Span {
+        let before = &message[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Span {
+            start,
+            end,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// A part of a commit message (e.g. a line or the scope) and its position in the message
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Part<'a> {
+    /// Byte offset of the part in the message
+    pub start: usize,
+    /// The text of the part
+    pub text: &'a str,
+}
+
+impl<'a> Part<'a> {
+    /// Creates a part with the text starting at byte `start` in the message
+    pub fn new(start: usize, text: &'a str) -> Part<'a> {
+        Part { start, text }
+    }
+
+    /// Byte offset of the end (exclusive) of the part in the message
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    /// Span of the byte range `from..to` relative to the start of the part
+    pub fn span(&self, message: &str, from: usize, to: usize) -> Span {
+        Span::new(message, self.start + from, self.start + to)
+    }
+
+    /// Span of the whole part
+    pub fn full_span(&self, message: &str) -> Span {
+        self.span(message, 0, self.text.len())
+    }
+
+    /// Splits the part into its lines, line endings (`\n` or `\r\n`) are not part of the lines
+    pub fn lines(&self) -> Vec<Part<'a>> {
+        let mut lines = Vec::<Part>::new();
+        let mut offset = self.start;
+
+        for line in self.text.split_inclusive('\n') {
+            lines.push(Part::new(
+                offset,
+                line.trim_end_matches('\n').trim_end_matches('\r'),
+            ));
+            offset += line.len();
+        }
+
+        lines
+    }
+}
@@ -1,23 +1,81 @@
-use crate::conventional_commit::lint::Level;
+use crate::conventional_commit::lint::{Fix, Level, Span};
 use std::cmp::Ordering;
 use std::fmt;
 #[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Violation {
     pub level: Level,
+    /// Identifier of the rule that reported the violation (e.g. `header-max-length`)
+    pub rule: String,
     pub message: String,
     pub description: Option<String>,
+    /// Location of the violation in the linted message, `None` if the violation does not
+    /// refer to a specific part of the message (e.g. a missing body)
+    pub span: Option<Span>,
+    /// Suggested change that resolves the violation, `None` if it can not be fixed automatically
+    pub fix: Option<Fix>,
+}
+
+impl Violation {
+    /// Renders the line of the message the violation refers to, with the offending part
+    /// underlined by carets
+    ///
+    /// `message` must be the message that was linted, returns `None` if the violation has no span
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Commit;
+    /// let message = "feat: add parser.";
+    /// let result = Commit::lint(message);
+    /// let lint = result.lints.iter().find(|l| l.rule == "subject-full-stop").unwrap();
+    ///
+    /// assert_eq!(
+    ///     lint.snippet(message).unwrap(),
+    ///     " --> 1:17\n  |\n1 | feat: add parser.\n  |                 ^"
+    /// );
+    /// ```
+    pub fn snippet(&self, message: &str) -> Option<String> {
+        let span = self.span?;
+
+        let line_start = message[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line = message[line_start..]
+            .split('\n')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('\r');
+        let line_end = line_start + line.len();
+
+        let underlined = if span.start < line_end {
+            message[span.start..span.end.min(line_end)].chars().count()
+        } else {
+            0
+        };
+
+        let number = span.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        Some(format!(
+            "{gutter}--> {}:{}\n{gutter} |\n{number} | {line}\n{gutter} | {}{}",
+            span.line,
+            span.column,
+            " ".repeat(span.column - 1),
+            "^".repeat(underlined.max(1)),
+        ))
+    }
 }
 
 impl fmt::Display for Violation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{}: {}{}",
+            "{}{:?}: {}{}",
+            self.level,
             self.level,
             self.message,
             self.description
                 .as_ref()
-                .map_or_else(String::new, |d| { format!("\n\t\t     {}", d) })
+                .map_or_else(String::new, |d| { format!("\n\t{}", d) })
         )
     }
 }
@@ -37,14 +95,17 @@ impl PartialOrd for Violation {
 #[cfg(test)]
 mod format_test {
 
-    use crate::conventional_commit::lint::{Level, Violation};
+    use crate::conventional_commit::lint::{Level, Span, Violation};
 
     #[test]
     fn test_format() {
         let lint = Violation {
             level: Level::Error,
+            rule: String::from("test"),
             message: String::from("Something happened"),
             description: None,
+            span: None,
+            fix: None,
         };
 
         assert_eq!(format!("{lint}"), "❌ Error: Something happened");
@@ -53,12 +114,51 @@ mod format_test {
     fn test_format_description() {
         let lint = Violation {
             level: Level::Error,
+            rule: String::from("test"),
             message: String::from("Something happened"),
             description: Some(String::from(
                 "This is an error and should not happen! Make sure you do it right next time",
             )),
+            span: None,
+            fix: None,
         };
 
         assert_eq!(format!("{lint}"), "❌ Error: Something happened\n\tThis is an error and should not happen! Make sure you do it right next time");
     }
+
+    #[test]
+    fn test_snippet() {
+        let message = "fix: remove typo\n\nThe typo was in the readme";
+        let lint = Violation {
+            level: Level::Warning,
+            rule: String::from("test"),
+            message: String::from("Something happened"),
+            description: None,
+            span: Some(Span::new(message, 22, 26)),
+            fix: None,
+        };
+
+        assert_eq!(
+            lint.snippet(message).unwrap(),
+            " --> 3:5\n  |\n3 | The typo was in the readme\n  |     ^^^^"
+        );
+    }
+
+    #[test]
+    fn test_snippet_empty_span() {
+        let message = "feat:";
+        let lint = Violation {
+            level: Level::Error,
+            rule: String::from("test"),
+            message: String::from("Something happened"),
+            description: None,
+            span: Some(Span::new(message, 5, 5)),
+            fix: None,
+        };
+
+        assert_eq!(
+            lint.snippet(message).unwrap(),
+            " --> 1:6\n  |\n1 | feat:\n  |      ^"
+        );
+    }
 }
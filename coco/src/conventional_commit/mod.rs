@@ -1,6 +1,7 @@
 mod commit;
 mod commit_type;
 pub mod lint;
+pub mod parser;
 
 pub use commit::Commit;
-pub use commit_type::CommitType;
+pub use commit_type::{CommitType, ParseCommitTypeError};
@@ -0,0 +1,57 @@
+use crate::lint::Part;
+
+/// A commit message parsed into the parts defined by the conventional commit specification
+///
+/// All parts refer to the parsed message and contain their position in it.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Message<'a> {
+    /// The first line of the message
+    pub header: Header<'a>,
+    /// The body between the header and the footers, without the surrounding blank lines
+    pub body: Option<Part<'a>>,
+    /// The footers in the order they appear in the message
+    pub footers: Vec<Footer<'a>>,
+}
+
+/// The header of a commit message: `<type>[(scope)][!]: <description>`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Header<'a> {
+    /// The whole header line without the line ending
+    pub line: Part<'a>,
+    /// The type of the commit as written in the message (e.g. `feat`)
+    pub commit_type: Part<'a>,
+    /// The text between the parenthesis, `None` if no scope is given
+    pub scope: Option<Part<'a>>,
+    /// The scopes in the parenthesis that are separated by commas, without surrounding whitespace
+    pub scopes: Vec<Part<'a>>,
+    /// The `!` that marks a breaking change
+    pub bang: Option<Part<'a>>,
+    /// The description following the colon and the space after it
+    pub description: Part<'a>,
+}
+
+/// A footer of a commit message: `<token>: <value>` or `<token> #<value>`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Footer<'a> {
+    /// The token of the footer (e.g. `Reviewed-by` or `BREAKING CHANGE`)
+    pub token: Part<'a>,
+    /// The separator between token and value, either `: ` or ` #`
+    pub separator: Part<'a>,
+    /// The value of the footer, which can span multiple lines
+    pub value: Part<'a>,
+}
+
+impl<'a> Message<'a> {
+    /// Checks if the message is marked as breaking change, either by a `!` in the header
+    /// or by a `BREAKING CHANGE` footer
+    pub fn is_breaking(&self) -> bool {
+        self.header.bang.is_some() || self.footers.iter().any(|f| f.is_breaking())
+    }
+}
+
+impl<'a> Footer<'a> {
+    /// Checks if the footer describes a breaking change (`BREAKING CHANGE` or `BREAKING-CHANGE`)
+    pub fn is_breaking(&self) -> bool {
+        self.token.text == "BREAKING CHANGE" || self.token.text == "BREAKING-CHANGE"
+    }
+}
@@ -0,0 +1,453 @@
+//! Parser for commit messages according to the
+//! [conventional commit specification](https://www.conventionalcommits.org/en/v1.0.0/#specification)
+//!
+//! The parser recovers from most errors in the header (e.g. a missing space after the colon),
+//! so that every failure is reported as a specific [Violation] and the rest of the message
+//! can still be linted.
+mod ast;
+
+use crate::lint::{Fix, Level, Part, Span, Violation};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+pub use ast::{Footer, Header, Message};
+
+lazy_static! {
+    /// Matches the start of a footer (`token: value` or `token #value`), only BREAKING CHANGE
+    /// may contain whitespace, so a sentence like `Note that: ...` stays in the body
+    static ref FOOTER_RE: Regex = Regex::new(r"^(BREAKING CHANGE|[A-Za-z][\w-]*)(: | #)").unwrap();
+}
+
+/// The result of parsing a commit message
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Parsed<'a> {
+    /// The parsed message, `None` if the header could not be parsed at all
+    pub message: Option<Message<'a>>,
+    /// Violations of the specification found while parsing, all with the level [Level::Error]
+    pub violations: Vec<Violation>,
+}
+
+/// Parses a commit message into its [Message] representation
+///
+/// Line endings can be either `\n` or `\r\n`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use coco::parser;
+/// let parsed = parser::parse("feat(api,cli)!: add version command\r\n\r\nRefs: #12\r\n");
+/// let message = parsed.message.unwrap();
+///
+/// assert!(parsed.violations.is_empty());
+/// assert_eq!(message.header.commit_type.text, "feat");
+/// assert_eq!(message.header.scopes.len(), 2);
+/// assert_eq!(message.footers[0].value.text, "#12");
+///
+/// let parsed = parser::parse("feat:add version command");
+/// assert_eq!(parsed.violations[0].message, "Missing space after the colon");
+/// ```
+pub fn parse(message: &str) -> Parsed<'_> {
+    let mut violations = Vec::<Violation>::new();
+    let lines = Part::new(0, message).lines();
+    let header_line = lines.first().copied().unwrap_or(Part::new(0, ""));
+
+    let header = match parse_header(message, header_line, &mut violations) {
+        Some(h) => h,
+        None => {
+            return Parsed {
+                message: None,
+                violations,
+            }
+        }
+    };
+
+    let (body, footers) = parse_body(message, lines.get(1..).unwrap_or_default());
+
+    Parsed {
+        message: Some(Message {
+            header,
+            body,
+            footers,
+        }),
+        violations,
+    }
+}
+
+fn error(rule: &str, message: &str, description: &str, span: Span) -> Violation {
+    Violation {
+        level: Level::Error,
+        rule: String::from(rule),
+        message: String::from(message),
+        description: Some(String::from(description)),
+        span: Some(span),
+        fix: None,
+    }
+}
+
+fn parse_header<'a>(
+    message: &'a str,
+    line: Part<'a>,
+    violations: &mut Vec<Violation>,
+) -> Option<Header<'a>> {
+    let text = line.text;
+
+    if text.trim().is_empty() {
+        violations.push(error(
+            "header-format",
+            "Header is empty",
+            "The first line of the commit message must be the header: <type>[optional scope]: <description>",
+            line.full_span(message),
+        ));
+        return None;
+    }
+
+    // type
+    let type_end = if text.starts_with("BREAKING CHANGE") {
+        "BREAKING CHANGE".len()
+    } else {
+        text.find(|c: char| matches!(c, '(' | '!' | ':') || c.is_whitespace())
+            .unwrap_or(text.len())
+    };
+    let commit_type = Part::new(line.start, &text[..type_end]);
+
+    if commit_type.text.is_empty() {
+        violations.push(error(
+            "type-empty",
+            "Mandatory commit type is missing",
+            "Make sure you provide a commit type that describes of what type the change is (e.g. fix, feat, BREAKING CHANGE). Type must be ascii letters only",
+            line.span(message, 0, 0),
+        ));
+    } else if commit_type.text != "BREAKING CHANGE"
+        && !commit_type.text.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        violations.push(error(
+            "type-format",
+            "Type must only contain ascii letters",
+            "Digits, punctuation and other characters are not allowed in the type (e.g. use `fix` instead of `fix2`)",
+            commit_type.full_span(message),
+        ));
+    }
+
+    let mut i = type_end;
+    let mut bang = None;
+
+    if text[i..].starts_with("!(") {
+        bang = Some(Part::new(line.start + i, "!"));
+        violations.push(error(
+            "breaking-position",
+            "Breaking change marker `!` must follow the scope",
+            "Place the `!` directly before the colon (e.g. `feat(api)!: ...`)",
+            line.span(message, i, i + 1),
+        ));
+        i += 1;
+    }
+
+    // scope
+    let mut scope = None;
+    let mut scopes = Vec::<Part>::new();
+
+    if text[i..].starts_with('(') {
+        let close = match text[i..].find(')') {
+            Some(c) => i + c,
+            None => {
+                let description = if message[line.end()..].contains(')') {
+                    "The scope must not contain a line break, the whole header must be on the first line"
+                } else {
+                    "Close the scope with a parenthesis (e.g. `feat(api): ...`)"
+                };
+                violations.push(error(
+                    "scope-format",
+                    "Scope is not closed",
+                    description,
+                    line.span(message, i, text.len()),
+                ));
+                return None;
+            }
+        };
+
+        let inner = Part::new(line.start + i + 1, &text[i + 1..close]);
+
+        if inner.text.trim().is_empty() {
+            violations.push(error(
+                "scope-empty",
+                "Scope is empty",
+                "Scope is an optional parameter, but if not given the parenthesis must be removed",
+                line.span(message, i, close + 1),
+            ));
+        } else {
+            let mut offset = inner.start;
+
+            for s in inner.text.split(',') {
+                let leading = s.len() - s.trim_start().len();
+                scopes.push(Part::new(offset + leading, s.trim()));
+                offset += s.len() + 1;
+            }
+
+            if let Some(empty) = scopes.iter().find(|s| s.text.is_empty()) {
+                violations.push(error(
+                    "scope-empty",
+                    "Scope list contains an empty scope",
+                    "Multiple scopes must be separated by a single comma (e.g. `feat(api,cli): ...`)",
+                    empty.full_span(message),
+                ));
+            }
+        }
+
+        scope = Some(inner);
+        i = close + 1;
+    }
+
+    if bang.is_none() && text[i..].starts_with('!') {
+        bang = Some(Part::new(line.start + i, "!"));
+        i += 1;
+    }
+
+    // separator
+    if !text[i..].starts_with(':') {
+        match text[i..].find(':') {
+            Some(colon) => {
+                violations.push(error(
+                    "header-separator",
+                    "Unexpected characters before the colon",
+                    "The type, scope and `!` must be directly followed by the colon (e.g. `feat(api)!: ...`)",
+                    line.span(message, i, i + colon),
+                ));
+                i += colon;
+            }
+            None => {
+                violations.push(error(
+                    "header-separator",
+                    "Colon after the type is missing",
+                    "The header must follow the specification: <type>[optional scope]: <description>",
+                    line.span(message, i, text.len()),
+                ));
+                return None;
+            }
+        }
+    }
+
+    i += 1;
+
+    if text[i..].starts_with(' ') {
+        i += 1;
+    } else if !text[i..].is_empty() {
+        let mut violation = error(
+            "header-separator",
+            "Missing space after the colon",
+            "The colon must be followed by a single space (e.g. `feat: add parser`)",
+            line.span(message, i - 1, i),
+        );
+        violation.fix = Some(Fix::new(line.span(message, i, i), " "));
+        violations.push(violation);
+    }
+
+    let description = Part::new(line.start + i, &text[i..]);
+
+    if description.text.trim().is_empty() {
+        violations.push(error(
+            "subject-empty",
+            "Mandatory description is missing",
+            "The short description of the commit is missing; this is mandatory field and must be provided",
+            description.span(message, 0, 0),
+        ));
+    }
+
+    Some(Header {
+        line,
+        commit_type,
+        scope,
+        scopes,
+        bang,
+        description,
+    })
+}
+
+/// Parses the lines following the header into body and footers, the footers are the last
+/// paragraph of the message if it starts with a footer token
+fn parse_body<'a>(message: &'a str, lines: &[Part<'a>]) -> (Option<Part<'a>>, Vec<Footer<'a>>) {
+    let mut end = lines.len();
+    while end > 0 && lines[end - 1].text.trim().is_empty() {
+        end -= 1;
+    }
+
+    let mut start = end;
+    while start > 0 && !lines[start - 1].text.trim().is_empty() {
+        start -= 1;
+    }
+
+    let (body, footer) = if start < end && FOOTER_RE.is_match(lines[start].text) {
+        (&lines[..start], &lines[start..end])
+    } else {
+        (&lines[..end], &lines[end..end])
+    };
+
+    let first = body.iter().find(|l| !l.text.trim().is_empty());
+    let last = body.iter().rev().find(|l| !l.text.trim().is_empty());
+
+    let body = match (first, last) {
+        (Some(f), Some(l)) => Some(Part::new(f.start, &message[f.start..l.end()])),
+        _ => None,
+    };
+
+    let mut footers = Vec::<Footer>::new();
+
+    for line in footer {
+        match FOOTER_RE.captures(line.text) {
+            Some(caps) => {
+                let token = caps.get(1).unwrap();
+                let separator = caps.get(2).unwrap();
+
+                footers.push(Footer {
+                    token: Part::new(line.start, token.as_str()),
+                    separator: Part::new(line.start + separator.start(), separator.as_str()),
+                    value: Part::new(line.start + separator.end(), &line.text[separator.end()..]),
+                })
+            }
+            None => {
+                // continuation of the value of the previous footer
+                if let Some(f) = footers.last_mut() {
+                    f.value = Part::new(f.value.start, &message[f.value.start..line.end()]);
+                }
+            }
+        }
+    }
+
+    (body, footers)
+}
+
+#[cfg(test)]
+mod parse_test {
+
+    use crate::parser::parse;
+
+    fn violated(message: &str) -> Vec<String> {
+        parse(message)
+            .violations
+            .into_iter()
+            .map(|v| v.rule)
+            .collect()
+    }
+
+    #[test]
+    fn header() {
+        let parsed = parse("feat(api, cli)!: add version command");
+        let header = parsed.message.unwrap().header;
+
+        assert!(parsed.violations.is_empty());
+        assert_eq!(header.commit_type.text, "feat");
+        assert_eq!(header.scope.unwrap().text, "api, cli");
+        assert_eq!(
+            header.scopes.iter().map(|s| s.text).collect::<Vec<&str>>(),
+            vec!["api", "cli"]
+        );
+        assert_eq!(header.scopes[1].start, 10);
+        assert_eq!(header.bang.unwrap().start, 14);
+        assert_eq!(header.description.text, "add version command");
+        assert_eq!(header.description.start, 17);
+    }
+
+    #[test]
+    fn body_and_footers() {
+        let message = "fix: prevent racing\n\nIntroduce a request id.\n\nRemove timeouts.\n\nReviewed-by: Z\nRefs #123\nBREAKING CHANGE: the id\nis required\n";
+        let parsed = parse(message).message.unwrap();
+
+        assert_eq!(
+            parsed.body.unwrap().text,
+            "Introduce a request id.\n\nRemove timeouts."
+        );
+        assert_eq!(parsed.footers.len(), 3);
+        assert_eq!(parsed.footers[0].token.text, "Reviewed-by");
+        assert_eq!(parsed.footers[1].separator.text, " #");
+        assert_eq!(parsed.footers[1].value.text, "123");
+        assert_eq!(parsed.footers[2].value.text, "the id\nis required");
+        assert!(parsed.is_breaking());
+    }
+
+    #[test]
+    fn spaced_token_in_body() {
+        let message = "fix: prevent racing\n\nNote that: the id\nis required\n\nSee also #12";
+        let parsed = parse(message).message.unwrap();
+
+        assert_eq!(
+            parsed.body.unwrap().text,
+            "Note that: the id\nis required\n\nSee also #12"
+        );
+        assert!(parsed.footers.is_empty());
+
+        let parsed = parse("fix: prevent racing\n\nNote that: the id\n\nRefs: #12")
+            .message
+            .unwrap();
+
+        assert_eq!(parsed.body.unwrap().text, "Note that: the id");
+        assert_eq!(parsed.footers[0].token.text, "Refs");
+    }
+
+    #[test]
+    fn crlf_line_endings() {
+        let parsed =
+            parse("fix: prevent racing\r\n\r\nIntroduce a request id.\r\n\r\nRefs: #1\r\n")
+                .message
+                .unwrap();
+
+        assert_eq!(parsed.header.description.text, "prevent racing");
+        assert_eq!(parsed.body.unwrap().text, "Introduce a request id.");
+        assert_eq!(parsed.footers[0].value.text, "#1");
+    }
+
+    #[test]
+    fn missing_space_after_colon() {
+        let parsed = parse("feat:add parser");
+
+        assert_eq!(violated("feat:add parser"), vec!["header-separator"]);
+        assert_eq!(parsed.violations[0].span.unwrap().start, 4);
+        assert_eq!(
+            parsed.message.unwrap().header.description.text,
+            "add parser"
+        );
+    }
+
+    #[test]
+    fn type_with_digits() {
+        assert_eq!(violated("fix2: remove typo"), vec!["type-format"]);
+    }
+
+    #[test]
+    fn missing_type() {
+        assert_eq!(violated(": remove typo"), vec!["type-empty"]);
+        assert_eq!(violated("(api): remove typo"), vec!["type-empty"]);
+    }
+
+    #[test]
+    fn bang_before_scope() {
+        let parsed = parse("feat!(api): add parser");
+
+        assert_eq!(parsed.violations[0].rule, "breaking-position");
+        assert!(parsed.message.unwrap().header.bang.is_some());
+    }
+
+    #[test]
+    fn newline_in_scope() {
+        let parsed = parse("feat(api\n): add parser");
+
+        assert_eq!(parsed.violations[0].rule, "scope-format");
+        assert_eq!(
+            parsed.violations[0].description.as_deref(),
+            Some("The scope must not contain a line break, the whole header must be on the first line")
+        );
+        assert!(parsed.message.is_none());
+    }
+
+    #[test]
+    fn missing_colon() {
+        assert_eq!(violated("feat add parser"), vec!["header-separator"]);
+        assert!(parse("feat add parser").message.is_none());
+        assert_eq!(violated("feat (api): add parser"), vec!["header-separator"]);
+    }
+
+    #[test]
+    fn empty_description() {
+        assert_eq!(violated("feat: "), vec!["subject-empty"]);
+        assert_eq!(violated("feat:"), vec!["subject-empty"]);
+        assert_eq!(violated(""), vec!["header-format"]);
+    }
+}
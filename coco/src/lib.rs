@@ -1,8 +1,8 @@
 mod conventional_commit;
 mod semantic_version;
 
-pub use conventional_commit::{lint, Commit, CommitType};
-pub use semantic_version::Version;
+pub use conventional_commit::{lint, parser, Commit, CommitType, ParseCommitTypeError};
+pub use semantic_version::{Bump, Channel, ChannelError, ParseVersionError, Version};
 
 #[cfg(test)]
 mod tests {}
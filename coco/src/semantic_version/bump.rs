@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// The level by which a version is incremented, ordered from no change to a major change
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Bump {
+    /// The version does not change
+    #[default]
+    None,
+    /// Backwards compatible bug fixes increment the patch version
+    Patch,
+    /// Backwards compatible features increment the minor version
+    Minor,
+    /// Breaking changes increment the major version
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bump::None => write!(f, "none"),
+            Bump::Patch => write!(f, "patch"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Major => write!(f, "major"),
+        }
+    }
+}
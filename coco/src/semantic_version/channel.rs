@@ -0,0 +1,442 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{Bump, Version};
+use crate::Commit;
+
+/// A release channel, which defines how versions are released from the branches matching
+/// a pattern
+///
+/// Stable versions are released from a channel without a pre-release identifier, the other
+/// channels release pre-releases numbered per identifier (e.g. `1.3.0-beta.2`). Maintenance
+/// channels limit the allowed bump, so the versions stay below the next release of the line.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Channel {
+    /// The pattern of the branch names, `*` matches any characters (e.g. `release/*`)
+    pub branch: String,
+    /// The pre-release identifier of the versions (e.g. `beta`), `None` for stable versions
+    pub pre_release: Option<String>,
+    /// The highest bump allowed on the channel
+    pub max_bump: Bump,
+}
+
+/// The reason a version can not be released on a [Channel]
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum ChannelError {
+    /// The commits require a bump above the highest bump allowed on the channel
+    BumpNotAllowed {
+        /// The bump required by the commits
+        required: Bump,
+        /// The highest bump allowed on the channel
+        allowed: Bump,
+    },
+    /// The version is not below the next release that already exists, which is the lowest
+    /// released version above the latest release of the channel
+    AboveNextRelease(Version),
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChannelError::BumpNotAllowed { required, allowed } => write!(
+                f,
+                "The commits require a {} bump, but only up to a {} bump is allowed",
+                required, allowed
+            ),
+            ChannelError::AboveNextRelease(release) => write!(
+                f,
+                "The version must stay below {}, which has already been released",
+                release
+            ),
+        }
+    }
+}
+
+impl Error for ChannelError {}
+
+impl Channel {
+    /// Returns a channel releasing stable versions from the branches matching the pattern
+    pub fn new(branch: &str) -> Channel {
+        Channel {
+            branch: branch.to_string(),
+            pre_release: None,
+            max_bump: Bump::Major,
+        }
+    }
+
+    /// Checks if the branch name matches the pattern of the channel
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Channel;
+    /// let channel = Channel::new("release/*");
+    ///
+    /// assert!(channel.matches("release/1.2"));
+    /// assert!(!channel.matches("main"));
+    /// ```
+    pub fn matches(&self, branch: &str) -> bool {
+        let mut parts = self.branch.split('*');
+        let mut rest = match branch.strip_prefix(parts.next().unwrap_or_default()) {
+            Some(r) => r,
+            None => return false,
+        };
+        let parts: Vec<&str> = parts.collect();
+
+        // The parts between the wildcards match at their first occurrence, the last part must
+        // end the branch name
+        match parts.split_last() {
+            None => rest.is_empty(),
+            Some((last, middle)) => {
+                for part in middle {
+                    match rest.find(part) {
+                        Some(i) => rest = &rest[i + part.len()..],
+                        None => return false,
+                    }
+                }
+                rest.ends_with(last)
+            }
+        }
+    }
+
+    /// Returns the first channel that matches the branch name
+    pub fn find<'a>(channels: &'a [Channel], branch: &str) -> Option<&'a Channel> {
+        channels.iter().find(|c| c.matches(branch))
+    }
+
+    /// Computes the next version released on the channel
+    ///
+    /// `latest` is the latest stable release of the channel and `commits` are the commits since
+    /// then, which bump the version like [Commit::bump]. `releases` are all released versions
+    /// of the repository, used to number the pre-releases and to keep the version below the
+    /// next release. Without a commit that bumps the version, `latest` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::{Bump, Channel, Commit, Version};
+    /// let channel = Channel {
+    ///     branch: String::from("next"),
+    ///     pre_release: Some(String::from("beta")),
+    ///     max_bump: Bump::Major,
+    /// };
+    /// let latest = Version::parse("1.2.0").unwrap();
+    /// let releases = [latest.clone(), Version::parse("1.3.0-beta.1").unwrap()];
+    /// let commits = [Commit::parse("feat: new feature").unwrap()];
+    ///
+    /// assert_eq!(
+    ///     channel.next_version(&latest, &releases, &commits),
+    ///     Ok(Version::parse("1.3.0-beta.2").unwrap())
+    /// );
+    /// ```
+    pub fn next_version(
+        &self,
+        latest: &Version,
+        releases: &[Version],
+        commits: &[Commit],
+    ) -> Result<Version, ChannelError> {
+        match commits.iter().map(Commit::bump_level).max() {
+            Some(bump) if bump != Bump::None => self.release(latest, releases, bump),
+            _ => Ok(latest.clone()),
+        }
+    }
+
+    /// Computes the development snapshot of the next version released on the channel, see
+    /// [Version::dev]
+    ///
+    /// Takes the same arguments as [Channel::next_version], with the number of commits since
+    /// `latest` as `distance` and the abbreviated hash of the snapshot commit. If no commit
+    /// bumps the version, the snapshot is one of the next patch release, so it still sorts
+    /// above `latest`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::{Channel, Commit, Version};
+    /// let latest = Version::parse("1.2.0").unwrap();
+    /// let commits = [Commit::parse("docs: readme").unwrap()];
+    ///
+    /// assert_eq!(
+    ///     Channel::new("main").dev_version(&latest, &[], &commits, 1, "1a2b3c"),
+    ///     Ok(Version::parse("1.2.1-alpha.1+g1a2b3c").unwrap())
+    /// );
+    /// ```
+    pub fn dev_version(
+        &self,
+        latest: &Version,
+        releases: &[Version],
+        commits: &[Commit],
+        distance: usize,
+        hash: &str,
+    ) -> Result<Version, ChannelError> {
+        let mut next = self.next_version(latest, releases, commits)?;
+
+        if distance > 0 && next == *latest {
+            next = self.release(latest, releases, Bump::Patch)?;
+        }
+
+        Ok(next.dev(distance, hash))
+    }
+
+    /// Bumps `latest` to the next version released on the channel
+    fn release(
+        &self,
+        latest: &Version,
+        releases: &[Version],
+        bump: Bump,
+    ) -> Result<Version, ChannelError> {
+        if bump > self.max_bump {
+            return Err(ChannelError::BumpNotAllowed {
+                required: bump,
+                allowed: self.max_bump,
+            });
+        }
+
+        let mut version = latest.clone();
+        version.increment(bump);
+
+        // Pre-releases of a version that has been released are not below the release either
+        if let Some(next_release) = releases
+            .iter()
+            .filter(|r| r.pre_release.is_none() && *r > latest)
+            .min()
+        {
+            if version >= *next_release {
+                return Err(ChannelError::AboveNextRelease(next_release.clone()));
+            }
+        }
+
+        if let Some(identifier) = &self.pre_release {
+            let number = releases
+                .iter()
+                .filter(|r| {
+                    (r.major, r.minor, r.patch) == (version.major, version.minor, version.patch)
+                })
+                .filter_map(|r| {
+                    r.pre_release
+                        .as_deref()?
+                        .strip_prefix(identifier)?
+                        .strip_prefix('.')?
+                        .parse::<u64>()
+                        .ok()
+                })
+                .max()
+                .unwrap_or_default();
+
+            version.pre_release = Some(format!("{}.{}", identifier, number + 1));
+        }
+
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod channel_test {
+
+    use crate::{Bump, Channel, ChannelError, Commit, Version};
+
+    fn versions(versions: &[&str]) -> Vec<Version> {
+        versions
+            .iter()
+            .map(|v| Version::parse(v).unwrap())
+            .collect()
+    }
+
+    fn commits(messages: &[&str]) -> Vec<Commit> {
+        messages.iter().map(|m| Commit::parse(m).unwrap()).collect()
+    }
+
+    fn maintenance() -> Channel {
+        Channel {
+            branch: String::from("release/*"),
+            pre_release: None,
+            max_bump: Bump::Patch,
+        }
+    }
+
+    #[test]
+    fn matches_pattern() {
+        assert!(Channel::new("main").matches("main"));
+        assert!(!Channel::new("main").matches("main2"));
+        assert!(Channel::new("release/*").matches("release/1.x"));
+        assert!(!Channel::new("release/*").matches("feature/release"));
+        assert!(Channel::new("v1.*").matches("v1.2"));
+        assert!(!Channel::new("v1.*").matches("v102"));
+        assert!(Channel::new("*").matches("feature/a"));
+        assert!(Channel::new("release/*/next").matches("release/2/next"));
+        assert!(!Channel::new("release/*/next").matches("release/2/next/3"));
+        assert!(Channel::new("a*a").matches("aa"));
+        assert!(!Channel::new("a*a").matches("a"));
+        assert!(Channel::new("*.x").matches("1.x.x"));
+    }
+
+    #[test]
+    fn find_first_match() {
+        let channels = vec![Channel::new("main"), maintenance(), Channel::new("*")];
+
+        assert_eq!(Channel::find(&channels, "release/1.2"), Some(&channels[1]));
+        assert_eq!(Channel::find(&channels, "feature"), Some(&channels[2]));
+        assert_eq!(Channel::find(&channels[..2], "feature"), None);
+    }
+
+    #[test]
+    fn stable_version() {
+        let latest = Version::parse("1.2.3").unwrap();
+
+        assert_eq!(
+            Channel::new("main").next_version(
+                &latest,
+                &versions(&["1.2.3"]),
+                &commits(&["fix: one", "feat: two", "docs: three"])
+            ),
+            Ok(Version::parse("1.3.0").unwrap())
+        );
+        assert_eq!(
+            Channel::new("main").next_version(&latest, &[], &commits(&["feat!: breaking"])),
+            Ok(Version::parse("2.0.0").unwrap())
+        );
+    }
+
+    #[test]
+    fn without_release_worthy_commits() {
+        let latest = Version::parse("1.2.3").unwrap();
+
+        assert_eq!(
+            Channel::new("main").next_version(&latest, &[], &commits(&["docs: readme"])),
+            Ok(latest.clone())
+        );
+        assert_eq!(
+            Channel::new("main").next_version(&latest, &[], &[]),
+            Ok(latest)
+        );
+    }
+
+    #[test]
+    fn numbered_pre_releases() {
+        let channel = Channel {
+            branch: String::from("next"),
+            pre_release: Some(String::from("beta")),
+            max_bump: Bump::Major,
+        };
+        let latest = Version::parse("1.2.3").unwrap();
+        let releases = versions(&[
+            "1.2.3",
+            "1.3.0-beta.1",
+            "1.3.0-beta.10",
+            "1.3.0-alpha.12",
+            "1.3.0-betax.20",
+            "2.0.0-beta.4",
+        ]);
+
+        assert_eq!(
+            channel.next_version(&latest, &releases, &commits(&["feat: one"])),
+            Ok(Version::parse("1.3.0-beta.11").unwrap())
+        );
+        assert_eq!(
+            channel.next_version(&latest, &releases, &commits(&["fix: one"])),
+            Ok(Version::parse("1.2.4-beta.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn dev_snapshot() {
+        let latest = Version::parse("1.2.0").unwrap();
+        let releases = versions(&["1.2.0"]);
+
+        let snapshot = Channel::new("main")
+            .dev_version(&latest, &releases, &commits(&["feat: one"]), 4, "1a2b3c4")
+            .unwrap();
+        assert_eq!(snapshot, Version::parse("1.3.0-alpha.4+g1a2b3c4").unwrap());
+        assert_eq!(
+            Channel::new("main").dev_version(&latest, &releases, &[], 0, "1a2b3c4"),
+            Ok(latest)
+        );
+    }
+
+    #[test]
+    fn dev_snapshot_without_bump() {
+        let latest = Version::parse("1.2.0").unwrap();
+        let chores = commits(&["docs: readme", "chore: cleanup", "ci: cache"]);
+
+        let snapshot = Channel::new("main")
+            .dev_version(&latest, &versions(&["1.2.0"]), &chores, 3, "1a2b3c4")
+            .unwrap();
+        assert_eq!(snapshot, Version::parse("1.2.1-alpha.3+g1a2b3c4").unwrap());
+        assert!(snapshot > latest);
+        assert!(snapshot < Version::parse("1.2.1").unwrap());
+
+        assert_eq!(
+            maintenance().dev_version(&latest, &[], &chores, 3, "1a2b3c4"),
+            Ok(Version::parse("1.2.1-alpha.3+g1a2b3c4").unwrap())
+        );
+    }
+
+    #[test]
+    fn dev_snapshot_on_pre_release_channel() {
+        let channel = Channel {
+            branch: String::from("next"),
+            pre_release: Some(String::from("beta")),
+            max_bump: Bump::Major,
+        };
+        let latest = Version::parse("1.2.0").unwrap();
+        let releases = versions(&["1.2.0", "1.3.0-beta.1"]);
+
+        let snapshot = channel
+            .dev_version(&latest, &releases, &commits(&["feat: one"]), 5, "1a2b3c4")
+            .unwrap();
+        assert_eq!(snapshot, Version::parse("1.3.0-beta.1.5+g1a2b3c4").unwrap());
+        assert!(snapshot > Version::parse("1.3.0-beta.1").unwrap());
+        assert!(snapshot < Version::parse("1.3.0-beta.2").unwrap());
+
+        let snapshot = channel
+            .dev_version(
+                &latest,
+                &releases,
+                &commits(&["docs: readme"]),
+                2,
+                "1a2b3c4",
+            )
+            .unwrap();
+        assert_eq!(snapshot, Version::parse("1.2.1-beta.0.2+g1a2b3c4").unwrap());
+        assert!(snapshot > latest);
+    }
+
+    #[test]
+    fn maintenance_bump_not_allowed() {
+        let latest = Version::parse("1.2.3").unwrap();
+
+        assert_eq!(
+            maintenance().next_version(&latest, &[], &commits(&["fix: one"])),
+            Ok(Version::parse("1.2.4").unwrap())
+        );
+        assert_eq!(
+            maintenance().next_version(&latest, &[], &commits(&["fix: one", "feat: two"])),
+            Err(ChannelError::BumpNotAllowed {
+                required: Bump::Minor,
+                allowed: Bump::Patch
+            })
+        );
+    }
+
+    #[test]
+    fn below_next_release() {
+        let latest = Version::parse("1.2.3").unwrap();
+        let releases = versions(&["1.2.3", "1.2.4-rc.1", "1.3.0", "2.0.0"]);
+
+        assert_eq!(
+            maintenance().next_version(&latest, &releases, &commits(&["fix: one"])),
+            Ok(Version::parse("1.2.4").unwrap())
+        );
+
+        let channel = Channel {
+            max_bump: Bump::Minor,
+            ..maintenance()
+        };
+        assert_eq!(
+            channel.next_version(&latest, &releases, &commits(&["feat: one"])),
+            Err(ChannelError::AboveNextRelease(
+                Version::parse("1.3.0").unwrap()
+            ))
+        );
+    }
+}
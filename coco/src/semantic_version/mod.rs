@@ -1,3 +1,7 @@
+mod bump;
+mod channel;
 mod version;
 
-pub use version::Version;
+pub use bump::Bump;
+pub use channel::{Channel, ChannelError};
+pub use version::{ParseVersionError, Version};
@@ -1,8 +1,11 @@
 #![warn(missing_docs)]
 
+use super::Bump;
 use regex::Regex;
 use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
 
 
@@ -149,6 +152,82 @@ impl Version {
 
         Some(semver)
     }
+
+    /// Increments the version by the bump, a major or minor bump resets the lower fields
+    ///
+    /// The pre-release and metadata are removed, unless the version does not change.
+    pub(crate) fn increment(&mut self, bump: Bump) {
+        match bump {
+            Bump::Major => {
+                let major = self.major + 1;
+
+                self.reset();
+                self.major = major;
+                return;
+            }
+            Bump::Minor => {
+                self.minor += 1;
+                self.patch = 0;
+            }
+            Bump::Patch => self.patch += 1,
+            Bump::None => return,
+        }
+
+        self.pre_release = None;
+        self.metadata = None;
+    }
+
+    /// Returns the development snapshot of the version between releases, like
+    /// `1.3.0-alpha.5+g1a2b3c` for the predicted next version `1.3.0`
+    ///
+    /// `distance` is the number of commits since the latest release and `hash` the
+    /// abbreviated hash of the snapshot commit, which is prefixed with `g` like `git describe`
+    /// does. The snapshot sorts below the version, so a stable version gets the pre-release
+    /// `alpha.<distance>`. A numbered pre-release like `beta.2` keeps its identifier and
+    /// becomes `beta.1.<distance>`, which sorts between `beta.1` and `beta.2`. Other
+    /// pre-releases like `rc` or `beta.0` can not be numbered down and are prefixed with the
+    /// numeric identifier `0` instead (e.g. `0.rc.<distance>`), which sorts below every
+    /// pre-release that does not start with `0` itself. Without commits since the release
+    /// (`distance` is 0) the version is the release itself and returned as it is.
+    ///
+    /// Use [Channel::dev_version](crate::Channel::dev_version) to compute the snapshot of the
+    /// next version of a channel, which also covers commits that do not bump the version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Version;
+    /// let next = Version::parse("1.3.0").unwrap();
+    /// let beta = Version::parse("1.3.0-beta.2").unwrap();
+    ///
+    /// assert_eq!(next.dev(5, "1a2b3c").to_string(), "1.3.0-alpha.5+g1a2b3c");
+    /// assert_eq!(beta.dev(5, "1a2b3c").to_string(), "1.3.0-beta.1.5+g1a2b3c");
+    /// assert_eq!(next.dev(0, "1a2b3c"), next);
+    /// ```
+    pub fn dev(&self, distance: usize, hash: &str) -> Version {
+        if distance == 0 {
+            return self.clone();
+        }
+
+        let pre_release = match &self.pre_release {
+            None => format!("alpha.{}", distance),
+            Some(pre_release) => match pre_release
+                .rsplit_once('.')
+                .and_then(|(id, n)| Some((id, n.parse::<u64>().ok()?)))
+            {
+                Some((identifier, number)) if number > 0 => {
+                    format!("{}.{}.{}", identifier, number - 1, distance)
+                }
+                _ => format!("0.{}.{}", pre_release, distance),
+            },
+        };
+
+        Version {
+            pre_release: Some(pre_release),
+            metadata: Some(format!("g{}", hash)),
+            ..self.clone()
+        }
+    }
 }
 
 impl fmt::Display for Version {
@@ -169,6 +248,176 @@ impl fmt::Display for Version {
     }
 }
 
+/// The error returned when a string is not a valid semantic version
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ParseVersionError {
+    version: String,
+    reason: &'static str,
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a semantic version, {}",
+            self.version, self.reason
+        )
+    }
+}
+
+impl Error for ParseVersionError {}
+
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    /// Parses a string that contains exactly one semantic version
+    ///
+    /// In contrast to [Version::parse] the whole string must be a valid version according to
+    /// the [specification](https://semver.org/#backusnaur-form-grammar-for-valid-semver-versions),
+    /// prefixes like `v` are not allowed and numbers must not have leading zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use coco::Version;
+    /// let version: Version = "1.2.3-alpha.1+d408340".parse().unwrap();
+    /// assert_eq!(version.pre_release.as_deref(), Some("alpha.1"));
+    ///
+    /// let error = "1.02.3".parse::<Version>().unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "\"1.02.3\" is not a semantic version, numbers must not have leading zeros"
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason| ParseVersionError {
+            version: s.to_string(),
+            reason,
+        };
+
+        let (rest, metadata) = match s.split_once('+') {
+            Some((r, m)) => (r, Some(m)),
+            None => (s, None),
+        };
+        let (core, pre_release) = match rest.split_once('-') {
+            Some((c, p)) => (c, Some(p)),
+            None => (rest, None),
+        };
+
+        let numbers = core.split('.').collect::<Vec<&str>>();
+        if numbers.len() != 3 {
+            return Err(error("major, minor and patch must be separated by a dot"));
+        }
+
+        let mut parsed = [0u64; 3];
+        for (i, number) in numbers.iter().enumerate() {
+            if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                return Err(error("major, minor and patch must be numbers"));
+            }
+            if number.len() > 1 && number.starts_with('0') {
+                return Err(error("numbers must not have leading zeros"));
+            }
+            parsed[i] = number.parse().map_err(|_| error("number is too large"))?;
+        }
+
+        if let Some(pre_release) = pre_release {
+            if !valid_identifiers(pre_release) {
+                return Err(error(
+                    "pre release must be dot separated alphanumeric identifiers",
+                ));
+            }
+            if pre_release
+                .split('.')
+                .any(|i| i.len() > 1 && i.starts_with('0') && i.chars().all(|c| c.is_ascii_digit()))
+            {
+                return Err(error("numbers must not have leading zeros"));
+            }
+        }
+
+        if metadata.is_some_and(|m| !valid_identifiers(m)) {
+            return Err(error(
+                "metadata must be dot separated alphanumeric identifiers",
+            ));
+        }
+
+        Ok(Version {
+            major: parsed[0],
+            minor: parsed[1],
+            patch: parsed[2],
+            pre_release: pre_release.map(String::from),
+            metadata: metadata.map(String::from),
+        })
+    }
+}
+
+/// Checks that the text consists of non empty identifiers of ascii alphanumerics and hyphens,
+/// separated by dots
+fn valid_identifiers(text: &str) -> bool {
+    text.split('.')
+        .all(|i| !i.is_empty() && i.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+}
+
+#[cfg(feature = "semver")]
+impl From<semver::Version> for Version {
+    fn from(version: semver::Version) -> Self {
+        Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre_release: (!version.pre.is_empty()).then(|| version.pre.to_string()),
+            metadata: (!version.build.is_empty()).then(|| version.build.to_string()),
+        }
+    }
+}
+
+/// Fails if the pre release or metadata are not valid according to the specification, which
+/// [Version::parse] does not check
+#[cfg(feature = "semver")]
+impl TryFrom<&Version> for semver::Version {
+    type Error = semver::Error;
+
+    fn try_from(version: &Version) -> Result<Self, Self::Error> {
+        Ok(semver::Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre: match &version.pre_release {
+                Some(p) => semver::Prerelease::new(p)?,
+                None => semver::Prerelease::EMPTY,
+            },
+            build: match &version.metadata {
+                Some(m) => semver::BuildMetadata::new(m)?,
+                None => semver::BuildMetadata::EMPTY,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "semver")]
+impl TryFrom<Version> for semver::Version {
+    type Error = semver::Error;
+
+    fn try_from(version: Version) -> Result<Self, Self::Error> {
+        semver::Version::try_from(&version)
+    }
+}
+
+/// Serializes the version in its string form (e.g. `"1.2.3-alpha+d408340"`)
+#[cfg(feature = "serde")]
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = String::deserialize(deserializer)?;
+        version.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 impl Ord for Version {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.major != other.major {
@@ -213,8 +462,17 @@ impl Ord for Version {
                 return Ordering::Greater;
             }
 
-            if s.cmp(&o) != Ordering::Equal {
-                return s.cmp(&o);
+            // Numeric identifiers are compared numerically and have a lower precedence than
+            // alphanumeric identifiers
+            let ordering = match (s.unwrap().parse::<u64>(), o.unwrap().parse::<u64>()) {
+                (Ok(s), Ok(o)) => s.cmp(&o),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => s.cmp(&o),
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
             }
         }
 
@@ -306,6 +564,14 @@ mod ordering_test {
         assert_eq!(version_greater.cmp(&version_less), Ordering::Greater);
     }
 
+    #[test]
+    fn pre_release_numeric_diff() {
+        let version_greater = Version::parse("1.2.3-beta.11").unwrap();
+        let version_less = Version::parse("1.2.3-beta.2").unwrap();
+
+        assert_eq!(version_greater.cmp(&version_less), Ordering::Greater);
+    }
+
     #[test]
     fn pre_release_sanity_check() {
         let version_greater = Version::parse("1.2.3-beta.11").unwrap();
@@ -424,3 +690,162 @@ mod parse_test {
         assert_eq!(format!("{version}"), "1.2.3");
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_test {
+
+    use crate::Version;
+
+    #[test]
+    fn string_form() {
+        let version = Version::parse("1.2.3-alpha+d408340").unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&version).unwrap(),
+            "\"1.2.3-alpha+d408340\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Version>("\"1.2.3-alpha+d408340\"").unwrap(),
+            version
+        );
+        assert!(serde_json::from_str::<Version>("\"1.2\"").is_err());
+    }
+}
+
+#[cfg(test)]
+mod from_str_test {
+
+    use crate::Version;
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            "1.2.3".parse::<Version>(),
+            Ok(Version::parse("1.2.3").unwrap())
+        );
+        assert_eq!(
+            "0.0.0-alpha.0.x-1+build.01".parse::<Version>(),
+            Ok(Version::parse("0.0.0-alpha.0.x-1+build.01").unwrap())
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for version in [
+            "",
+            "1.2",
+            "1.2.3.4",
+            "v1.2.3",
+            "1.2.3 ",
+            "01.2.3",
+            "1.2.3-",
+            "1.2.3-alpha..1",
+            "1.2.3-01",
+            "1.2.3+",
+            "1.2.3+build_1",
+            "1.2.99999999999999999999",
+        ] {
+            assert!(version.parse::<Version>().is_err(), "{} is valid", version);
+        }
+    }
+}
+
+#[cfg(test)]
+mod dev_test {
+
+    use crate::Version;
+
+    #[test]
+    fn snapshot() {
+        let next = Version::parse("1.3.0+build").unwrap();
+
+        assert_eq!(
+            next.dev(12, "1a2b3c4"),
+            Version::parse("1.3.0-alpha.12+g1a2b3c4").unwrap()
+        );
+    }
+
+    #[test]
+    fn pre_release_snapshot() {
+        let next = Version::parse("1.3.0-beta.2+build").unwrap();
+        let snapshot = next.dev(12, "1a2b3c4");
+
+        assert_eq!(
+            snapshot,
+            Version::parse("1.3.0-beta.1.12+g1a2b3c4").unwrap()
+        );
+        assert!(snapshot > Version::parse("1.3.0-beta.1").unwrap());
+        assert!(snapshot < next);
+
+        let first = Version::parse("1.3.0-beta.1").unwrap().dev(3, "a");
+        assert_eq!(first.pre_release.as_deref(), Some("beta.0.3"));
+        assert!(first > Version::parse("1.2.0").unwrap());
+        assert!(first < Version::parse("1.3.0-beta.1").unwrap());
+
+        let zero = Version::parse("1.3.0-beta.0").unwrap();
+        let snapshot = zero.dev(3, "a");
+        assert_eq!(snapshot.pre_release.as_deref(), Some("0.beta.0.3"));
+        assert!(snapshot < zero);
+        assert!(snapshot > Version::parse("1.2.0").unwrap());
+
+        let unnumbered = Version::parse("1.3.0-rc").unwrap();
+        let snapshot = unnumbered.dev(3, "a");
+        assert_eq!(snapshot.pre_release.as_deref(), Some("0.rc.3"));
+        assert!(snapshot < unnumbered);
+        assert!(snapshot < unnumbered.dev(10, "b"));
+        assert!(snapshot > Version::parse("1.2.0").unwrap());
+    }
+
+    #[test]
+    fn ordered_by_distance() {
+        let next = Version::parse("1.3.0").unwrap();
+
+        assert!(next.dev(2, "b") > next.dev(1, "a"));
+        assert!(next.dev(10, "a") > next.dev(9, "b"));
+        assert!(next.dev(1, "a") < next);
+        assert!(next.dev(1, "a") > Version::parse("1.2.9").unwrap());
+    }
+
+    #[test]
+    fn at_release() {
+        let release = Version::parse("1.2.0").unwrap();
+
+        assert_eq!(release.dev(0, "1a2b3c4"), release);
+    }
+}
+
+#[cfg(all(test, feature = "semver"))]
+mod semver_test {
+
+    use crate::Version;
+
+    #[test]
+    fn from_semver() {
+        let version = semver::Version::parse("1.2.3-alpha.1+d408340").unwrap();
+
+        assert_eq!(
+            Version::from(version),
+            Version::parse("1.2.3-alpha.1+d408340").unwrap()
+        );
+        assert_eq!(
+            Version::from(semver::Version::new(1, 2, 3)),
+            Version::parse("1.2.3").unwrap()
+        );
+    }
+
+    #[test]
+    fn to_semver() {
+        let version = Version::parse("1.2.3-alpha.1+d408340").unwrap();
+
+        assert_eq!(
+            semver::Version::try_from(&version).unwrap(),
+            semver::Version::parse("1.2.3-alpha.1+d408340").unwrap()
+        );
+
+        let invalid = Version {
+            pre_release: Some(String::from("alpha..1")),
+            ..version
+        };
+        assert!(semver::Version::try_from(invalid).is_err());
+    }
+}
@@ -0,0 +1,501 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use gix::bstr::BStr;
+use gix::diff::index::ChangeRef;
+use gix::object::tree::diff::ChangeDetached;
+use gix::revision::walk::Sorting;
+use gix::status::plumbing::index_as_worktree_with_renames::Summary;
+use gix::status::{index_worktree, Item, UntrackedFiles};
+use gix::traverse::commit::simple::CommitTimeOrder;
+
+use super::{normalize, GitBackend, ProcessBackend};
+use crate::core::{utility, Change, FileStatus};
+use crate::log::{CommitRecord, FileChange};
+use crate::{Error, Result};
+
+/// Runs every operation in-process with [gitoxide](https://github.com/GitoxideLabs/gitoxide),
+/// so no `git` binary is needed
+///
+/// Creating commits and fetching history is passed on to [ProcessBackend], as gitoxide does not
+/// run hooks and can not deepen a clone, so these operations still need the `git` binary.
+pub struct GixBackend {
+    repository: gix::Repository,
+    git_dir: PathBuf,
+    work_tree: Option<PathBuf>,
+}
+
+impl GitBackend for GixBackend {
+    fn open(path: &Path) -> Result<Self> {
+        let repository = gix::discover(normalize(path)?)
+            .map_err(|_| Error::NotARepository(path.to_path_buf()))?;
+
+        let git_dir = PathBuf::from(utility::normalize_pathname(repository.git_dir())?);
+        let work_tree = match repository.workdir() {
+            Some(w) => Some(PathBuf::from(utility::normalize_pathname(w)?)),
+            None => None,
+        };
+
+        Ok(GixBackend {
+            repository,
+            git_dir,
+            work_tree,
+        })
+    }
+
+    fn is_repository(path: &Path) -> Result<bool> {
+        match normalize(path) {
+            Ok(p) => Ok(gix::discover(p).is_ok()),
+            Err(Error::NotARepository(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn git_dir(&self) -> &Path {
+        &self.git_dir
+    }
+
+    fn work_tree(&self) -> Option<&Path> {
+        self.work_tree.as_deref()
+    }
+
+    fn log(&self, from: &str, to: &str, format: &str, amount: Option<usize>) -> Result<String> {
+        let mut log = String::new();
+
+        for commit in self.walk(from, to, amount)? {
+            log.push_str(&format_commit(&commit, format)?);
+            log.push('\n');
+        }
+
+        Ok(log)
+    }
+
+    fn commits(
+        &self,
+        from: &str,
+        to: &str,
+        amount: Option<usize>,
+        files: bool,
+    ) -> Result<Vec<CommitRecord>> {
+        let mut commits = Vec::<CommitRecord>::new();
+
+        for commit in self.walk(from, to, amount)? {
+            let author = commit.author().map_err(backend)?;
+
+            commits.push(CommitRecord {
+                hash: commit.id.to_string(),
+                author_name: author.name.to_string(),
+                author_email: author.email.to_string(),
+                timestamp: author.seconds(),
+                message: commit.message_raw().map_err(backend)?.to_string(),
+                files: match files {
+                    true => self.changed_files(&commit)?,
+                    false => Vec::new(),
+                },
+            });
+        }
+
+        Ok(commits)
+    }
+
+    fn tags(&self) -> Result<Vec<String>> {
+        let references = self.repository.references().map_err(backend)?;
+        let mut tags = Vec::<String>::new();
+
+        for tag in references.tags().map_err(backend)? {
+            tags.push(
+                tag.map_err(|e| Error::Backend(e.into()))?
+                    .name()
+                    .shorten()
+                    .to_string(),
+            );
+        }
+
+        tags.sort_unstable();
+        Ok(tags)
+    }
+
+    fn reachable_tags(&self, revision: &str) -> Result<Vec<String>> {
+        let walk = self
+            .repository
+            .rev_walk([self.resolve(revision)?])
+            .all()
+            .map_err(backend)?;
+
+        let mut reachable = HashSet::<gix::ObjectId>::new();
+
+        for info in walk {
+            reachable.insert(info.map_err(backend)?.id);
+        }
+
+        let references = self.repository.references().map_err(backend)?;
+        let mut tags = Vec::<String>::new();
+
+        for tag in references.tags().map_err(backend)? {
+            let mut tag = tag.map_err(|e| Error::Backend(e.into()))?;
+
+            // Tags of other objects than commits (e.g. trees) are never reachable
+            if let Ok(commit) = tag.peel_to_commit() {
+                if reachable.contains(&commit.id) {
+                    tags.push(tag.name().shorten().to_string());
+                }
+            }
+        }
+
+        tags.sort_unstable();
+        Ok(tags)
+    }
+
+    fn latest_tag(&self) -> Result<String> {
+        let head = self
+            .repository
+            .find_commit(self.resolve("HEAD")?)
+            .map_err(backend)?;
+
+        match head.describe().try_format().map_err(backend)? {
+            Some(f) => Ok(f.to_string()),
+            None => Err(Error::NoTagFound),
+        }
+    }
+
+    fn commit(&self, message: &str) -> Result<String> {
+        self.process()?.commit(message)
+    }
+
+    fn is_shallow(&self) -> Result<bool> {
+        self.repository.is_shallow().map_err(backend)
+    }
+
+    fn deepen(&self, depth: usize) -> Result<()> {
+        self.process()?.deepen(depth)
+    }
+
+    fn current_branch(&self) -> Result<Option<String>> {
+        Ok(self
+            .repository
+            .head_name()
+            .map_err(backend)?
+            .map(|n| n.shorten().to_string()))
+    }
+
+    fn head_commit(&self) -> Result<String> {
+        Ok(self.resolve("HEAD")?.to_string())
+    }
+
+    fn status(&self) -> Result<Vec<FileStatus>> {
+        let items = self
+            .repository
+            .status(gix::progress::Discard)
+            .map_err(backend)?
+            .untracked_files(UntrackedFiles::Files)
+            .into_iter(None)
+            .map_err(backend)?;
+
+        let mut files = BTreeMap::<String, FileStatus>::new();
+
+        for item in items {
+            match item.map_err(backend)? {
+                Item::TreeIndex(change) => {
+                    let staged = match &change {
+                        ChangeRef::Addition { .. } => Change::Added,
+                        ChangeRef::Deletion { .. } => Change::Deleted,
+                        ChangeRef::Modification { .. } => Change::Modified,
+                        ChangeRef::Rewrite {
+                            source_location,
+                            copy: true,
+                            ..
+                        } => Change::Copied(source_location.to_string()),
+                        ChangeRef::Rewrite {
+                            source_location, ..
+                        } => Change::Renamed(source_location.to_string()),
+                    };
+                    file_status(&mut files, change.location()).staged = Some(staged);
+                }
+                // Like git, a file moved in the work tree is listed as deleted and untracked
+                Item::IndexWorktree(index_worktree::Item::Rewrite {
+                    source,
+                    dirwalk_entry,
+                    ..
+                }) => {
+                    file_status(&mut files, source.rela_path()).unstaged = Some(Change::Deleted);
+                    let moved = file_status(&mut files, dirwalk_entry.rela_path.as_ref());
+                    moved.unstaged = Some(Change::Added);
+                    moved.untracked = true;
+                }
+                Item::IndexWorktree(item) => {
+                    let unstaged = match item.summary() {
+                        Some(Summary::Removed) => Change::Deleted,
+                        Some(Summary::Added | Summary::IntentToAdd) => Change::Added,
+                        Some(Summary::TypeChange) => Change::TypeChanged,
+                        Some(_) => Change::Modified,
+                        None => continue,
+                    };
+                    let file = file_status(&mut files, item.rela_path());
+                    file.unstaged = Some(unstaged);
+                    // like git, a file added with `git add --intent-to-add` is tracked
+                    file.untracked = item.summary() == Some(Summary::Added);
+                }
+            }
+        }
+
+        Ok(files.into_values().collect())
+    }
+
+    fn upstream(&self) -> Result<Option<String>> {
+        let Some(branch) = self.repository.head_name().map_err(backend)? else {
+            return Ok(None);
+        };
+
+        let upstream = match self
+            .repository
+            .branch_remote_tracking_ref_name(branch.as_ref(), gix::remote::Direction::Fetch)
+        {
+            Some(u) => u.map_err(backend)?,
+            None => return Ok(None),
+        };
+
+        // Like git, an upstream that was configured but never fetched does not count
+        match self
+            .repository
+            .try_find_reference(upstream.as_ref())
+            .map_err(backend)?
+        {
+            Some(_) => Ok(Some(upstream.shorten().to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn ahead_behind(&self, revision: &str) -> Result<(usize, usize)> {
+        let head = self.resolve("HEAD")?;
+        let base = self.resolve(revision)?;
+
+        Ok((self.count(head, base)?, self.count(base, head)?))
+    }
+}
+
+impl GixBackend {
+    /// The backend for the operations that are run with the `git` binary
+    fn process(&self) -> Result<ProcessBackend> {
+        ProcessBackend::open(self.root())
+    }
+
+    /// The commits of the range like `git log <from>..<to> -<amount>`, newest first
+    fn walk(&self, from: &str, to: &str, amount: Option<usize>) -> Result<Vec<gix::Commit<'_>>> {
+        let from = if from.is_empty() { "HEAD" } else { from };
+        let (tip, hidden) = if to.is_empty() {
+            (self.resolve(from)?, None)
+        } else {
+            (self.resolve(to)?, Some(self.resolve(from)?))
+        };
+
+        let walk = self
+            .repository
+            .rev_walk([tip])
+            .with_hidden(hidden)
+            .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+            .all()
+            .map_err(backend)?;
+
+        let mut commits = Vec::new();
+
+        for info in walk.take(amount.unwrap_or(usize::MAX)) {
+            commits.push(info.map_err(backend)?.object().map_err(backend)?);
+        }
+
+        Ok(commits)
+    }
+
+    /// The files the commit changed compared to its first parent, with renames detected like
+    /// `git log -M`, merge commits are skipped like git does
+    fn changed_files(&self, commit: &gix::Commit) -> Result<Vec<FileChange>> {
+        let parents: Vec<gix::Id> = commit.parent_ids().collect();
+
+        if parents.len() > 1 {
+            return Ok(Vec::new());
+        }
+
+        let tree = commit.tree().map_err(backend)?;
+        let parent_tree = match parents.first() {
+            Some(id) => Some(
+                id.object()
+                    .map_err(backend)?
+                    .peel_to_commit()
+                    .map_err(backend)?
+                    .tree()
+                    .map_err(backend)?,
+            ),
+            None => None,
+        };
+
+        let changes = self
+            .repository
+            .diff_tree_to_tree(
+                parent_tree.as_ref(),
+                &tree,
+                gix::diff::Options::default().with_rewrites(Some(Default::default())),
+            )
+            .map_err(backend)?;
+
+        let mut files = Vec::<FileChange>::new();
+
+        for change in changes {
+            let (path, change) = match change {
+                ChangeDetached::Addition {
+                    location,
+                    entry_mode,
+                    ..
+                } if !entry_mode.is_tree() => (location, Change::Added),
+                ChangeDetached::Deletion {
+                    location,
+                    entry_mode,
+                    ..
+                } if !entry_mode.is_tree() => (location, Change::Deleted),
+                ChangeDetached::Modification {
+                    location,
+                    previous_entry_mode,
+                    entry_mode,
+                    ..
+                } if !entry_mode.is_tree() => {
+                    if previous_entry_mode.is_link() != entry_mode.is_link() {
+                        (location, Change::TypeChanged)
+                    } else {
+                        (location, Change::Modified)
+                    }
+                }
+                ChangeDetached::Rewrite {
+                    source_location,
+                    location,
+                    copy,
+                    entry_mode,
+                    ..
+                } if !entry_mode.is_tree() => match copy {
+                    true => (location, Change::Copied(source_location.to_string())),
+                    false => (location, Change::Renamed(source_location.to_string())),
+                },
+                // Git lists the files of a directory, not the directory itself
+                _ => continue,
+            };
+
+            files.push(FileChange {
+                path: path.to_string(),
+                change,
+            });
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Counts the commits reachable from `tip`, but not from `hidden`
+    fn count(&self, tip: gix::ObjectId, hidden: gix::ObjectId) -> Result<usize> {
+        let walk = self
+            .repository
+            .rev_walk([tip])
+            .with_hidden([hidden])
+            .all()
+            .map_err(backend)?;
+
+        let mut count = 0;
+
+        for info in walk {
+            info.map_err(backend)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Resolves a revision (e.g. `HEAD` or a tag) to the id of a commit
+    fn resolve(&self, revision: &str) -> Result<gix::ObjectId> {
+        let id = match self.repository.rev_parse_single(revision) {
+            Ok(id) => id,
+            Err(_) if self.repository.head().map_err(backend)?.is_unborn() => {
+                return Err(Error::EmptyRepository)
+            }
+            Err(_) => return Err(Error::UnknownRevision(revision.to_string())),
+        };
+
+        Ok(id
+            .object()
+            .map_err(backend)?
+            .peel_to_commit()
+            .map_err(backend)?
+            .id)
+    }
+}
+
+/// Formats a commit like `git log --format`, supported are the placeholders `%H`, `%h`,
+/// `%an`, `%ae`, `%at`, `%cn`, `%ce`, `%ct`, `%s`, `%b`, `%B`, `%n` and `%%`
+///
+/// Unknown placeholders are written as they are, like git does.
+fn format_commit(commit: &gix::Commit, format: &str) -> Result<String> {
+    let author = commit.author().map_err(backend)?;
+    let committer = commit.committer().map_err(backend)?;
+    let message = commit.message_raw().map_err(backend)?.to_string();
+    let (subject, body) = split_message(&message);
+
+    let mut formatted = String::new();
+    let mut rest = format;
+
+    while let Some(i) = rest.find('%') {
+        formatted.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        let (value, len) = match rest.get(1..3).unwrap_or_default() {
+            "an" => (author.name.to_string(), 3),
+            "ae" => (author.email.to_string(), 3),
+            "at" => (author.seconds().to_string(), 3),
+            "cn" => (committer.name.to_string(), 3),
+            "ce" => (committer.email.to_string(), 3),
+            "ct" => (committer.seconds().to_string(), 3),
+            _ => match rest.get(1..2).unwrap_or_default() {
+                "H" => (commit.id.to_string(), 2),
+                "h" => (commit.short_id().map_err(backend)?.to_string(), 2),
+                "s" => (subject.clone(), 2),
+                "b" => (body.to_string(), 2),
+                "B" => (message.clone(), 2),
+                "n" => (String::from("\n"), 2),
+                "%" => (String::from("%"), 2),
+                _ => (String::from("%"), 1),
+            },
+        };
+
+        formatted.push_str(&value);
+        rest = &rest[len..];
+    }
+    formatted.push_str(rest);
+
+    Ok(formatted)
+}
+
+/// Splits a message like git into the subject, which is the first paragraph joined to one
+/// line, and the body
+fn split_message(message: &str) -> (String, &str) {
+    let message = message.trim_start_matches(['\n', '\r']);
+    let (subject, body) = message
+        .split_once("\n\n")
+        .or_else(|| message.split_once("\r\n\r\n"))
+        .unwrap_or((message, ""));
+
+    (
+        subject
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join(" "),
+        body.trim_start_matches(['\n', '\r']),
+    )
+}
+
+/// The status of the file in the map, which is added if it has no changes yet
+fn file_status<'a>(files: &'a mut BTreeMap<String, FileStatus>, path: &BStr) -> &'a mut FileStatus {
+    let path = path.to_string();
+
+    files
+        .entry(path.clone())
+        .or_insert_with(|| FileStatus::new(&path))
+}
+
+fn backend(e: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::Backend(Box::new(e))
+}
@@ -0,0 +1,117 @@
+#[cfg(feature = "gix")]
+mod gitoxide;
+mod process;
+
+#[cfg(feature = "gix")]
+pub use gitoxide::GixBackend;
+pub use process::ProcessBackend;
+
+use std::io;
+use std::path::Path;
+
+use crate::core::{utility, FileStatus};
+use crate::log::CommitRecord;
+use crate::{Error, Result};
+
+/// The backend used by [Repository::new](super::Repository::new), which is [GixBackend] if the
+/// `gix` feature is enabled and [ProcessBackend] otherwise
+#[cfg(feature = "gix")]
+pub type DefaultBackend = GixBackend;
+/// The backend used by [Repository::new](super::Repository::new), which is [GixBackend] if the
+/// `gix` feature is enabled and [ProcessBackend] otherwise
+#[cfg(not(feature = "gix"))]
+pub type DefaultBackend = ProcessBackend;
+
+/// The git operations a [Repository](super::Repository) is built on
+///
+/// Every method behaves like the git command of the same name, so the backends can be swapped
+/// without changing the results.
+pub trait GitBackend {
+    /// Opens the repository containing the given path, which can be a file or a directory
+    /// anywhere in the repository
+    fn open(path: &Path) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Checks wether or not the given path (file or directory) is in a repository
+    fn is_repository(path: &Path) -> Result<bool>
+    where
+        Self: Sized;
+
+    /// The directory that contains the objects and references of the repository, e.g. the
+    /// `.git` directory or the repository itself if it is bare
+    fn git_dir(&self) -> &Path;
+
+    /// The directory with the checked out files, `None` if the repository is bare
+    ///
+    /// For a linked worktree or a submodule this is the directory of the worktree or submodule.
+    fn work_tree(&self) -> Option<&Path>;
+
+    /// The root path of the repository, which is the work tree or the git directory of a bare
+    /// repository
+    fn root(&self) -> &Path {
+        self.work_tree().unwrap_or_else(|| self.git_dir())
+    }
+
+    /// The history like `git log <from>..<to> --format=<format> -<amount>`, every commit is
+    /// formatted and followed by a newline
+    fn log(&self, from: &str, to: &str, format: &str, amount: Option<usize>) -> Result<String>;
+
+    /// The commits of the range like [GitBackend::log] as records, with the files changed by
+    /// each commit if `files` is set
+    fn commits(
+        &self,
+        from: &str,
+        to: &str,
+        amount: Option<usize>,
+        files: bool,
+    ) -> Result<Vec<CommitRecord>>;
+
+    /// All tags in the repository, sorted in alphanumerical order
+    fn tags(&self) -> Result<Vec<String>>;
+
+    /// The tags pointing to a commit reachable from the revision like `git tag --merged`,
+    /// sorted in alphanumerical order
+    fn reachable_tags(&self, revision: &str) -> Result<Vec<String>>;
+
+    /// The most recent annotated tag reachable from HEAD like `git describe`
+    fn latest_tag(&self) -> Result<String>;
+
+    /// Commits the staged changes with the given message and returns the output of git
+    fn commit(&self, message: &str) -> Result<String>;
+
+    /// Checks if the repository is a shallow clone, which only contains the recent history
+    fn is_shallow(&self) -> Result<bool>;
+
+    /// Fetches `depth` more commits of the history of a shallow clone, and the tags pointing
+    /// to them, like `git fetch --deepen=<depth>`
+    fn deepen(&self, depth: usize) -> Result<()>;
+
+    /// The name of the checked out branch, `None` if HEAD is detached
+    fn current_branch(&self) -> Result<Option<String>>;
+
+    /// The full hash of the commit HEAD points to
+    fn head_commit(&self) -> Result<String>;
+
+    /// The uncommitted changes in the work tree like `git status`, sorted by path
+    ///
+    /// Untracked files are listed individually, ignored files are left out.
+    fn status(&self) -> Result<Vec<FileStatus>>;
+
+    /// The remote tracking branch of the checked out branch (e.g. `origin/main`), `None` if
+    /// HEAD is detached or the branch has no upstream
+    fn upstream(&self) -> Result<Option<String>>;
+
+    /// The number of commits HEAD is ahead and behind of the revision, like
+    /// `git rev-list --left-right --count HEAD...<revision>`
+    fn ahead_behind(&self, revision: &str) -> Result<(usize, usize)>;
+}
+
+/// Normalizes the path a repository is opened at, a path that does not exist is not in a
+/// repository
+fn normalize(path: &Path) -> Result<String> {
+    utility::normalize_pathname(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::NotARepository(path.to_path_buf()),
+        _ => Error::Io(e),
+    })
+}
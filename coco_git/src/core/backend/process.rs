@@ -0,0 +1,445 @@
+use std::io;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{normalize, GitBackend};
+use crate::core::{git, Change, FileStatus};
+use crate::log::{CommitRecord, FileChange};
+use crate::{Error, Result};
+
+/// The details of a commit in the output of `git log -z` for [ProcessBackend::commits], each
+/// terminated by NUL which can not be part of a commit message
+const COMMIT_FORMAT: &str = "%H%x00%an%x00%ae%x00%at%x00%B";
+
+/// Runs every operation with the `git` binary, which must be installed and found by the system
+pub struct ProcessBackend {
+    git_dir: PathBuf,
+    work_tree: Option<PathBuf>,
+}
+
+impl ProcessBackend {
+    /// Runs git with the arguments on the repository and returns its output
+    ///
+    /// The git directory and work tree are passed explicitly, so bare repositories, linked
+    /// worktrees and submodules are handled the same way.
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let mut envs = vec![("GIT_DIR", self.git_dir.as_path())];
+
+        if let Some(work_tree) = &self.work_tree {
+            envs.push(("GIT_WORK_TREE", work_tree));
+        }
+
+        run(self.root(), &envs, args)
+    }
+
+    /// Checks if the revision exists and points to a commit
+    fn exists(&self, revision: &str) -> bool {
+        self.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", revision),
+        ])
+        .is_ok()
+    }
+
+    /// Runs `git log` on the range with the format and the additional options
+    fn run_log(
+        &self,
+        from: &str,
+        to: &str,
+        format: &str,
+        amount: Option<usize>,
+        options: &[&str],
+    ) -> Result<String> {
+        let mut args = vec![String::from("log")];
+
+        let mut range = from.to_string();
+
+        if !to.is_empty() {
+            range.push_str(format!("..{}", to).as_str());
+        }
+
+        if !range.is_empty() {
+            args.push(range);
+        }
+
+        if !format.is_empty() {
+            args.push(format!("--format={}", format));
+        }
+
+        if let Some(amount) = amount {
+            args.push(format!("-{}", amount));
+        };
+
+        args.extend(options.iter().map(|o| o.to_string()));
+
+        // Separates the revisions from paths, so git does not guess what an unknown revision is
+        args.push(String::from("--"));
+
+        match self.git(&args.iter().map(String::as_str).collect::<Vec<&str>>()) {
+            Err(Error::UnknownRevision(range)) => Err(self.unknown_revision(range, &[from, to])),
+            log => log,
+        }
+    }
+
+    /// Names the revision that does not exist, as git only names the whole range, or reports
+    /// an empty repository as git does not distinguish it from an unknown revision
+    fn unknown_revision(&self, range: String, revisions: &[&str]) -> Error {
+        if !self.exists("HEAD") {
+            return Error::EmptyRepository;
+        }
+
+        Error::UnknownRevision(
+            revisions
+                .iter()
+                .find(|r| !r.is_empty() && !self.exists(r))
+                .map_or(range, |r| r.to_string()),
+        )
+    }
+}
+
+impl GitBackend for ProcessBackend {
+    fn open(path: &Path) -> Result<Self> {
+        let cannon_path = normalize(path)?;
+        let dir = Path::new(&cannon_path);
+
+        let output = run(
+            dir,
+            &[],
+            &["rev-parse", "--absolute-git-dir", "--is-bare-repository"],
+        )?;
+        let mut lines = output.lines();
+
+        let git_dir = PathBuf::from(lines.next().unwrap_or_default());
+        let work_tree = match lines.next() {
+            Some("true") => None,
+            _ => Some(PathBuf::from(
+                run(dir, &[], &["rev-parse", "--show-toplevel"])?.trim_end_matches(['\n', '\r']),
+            )),
+        };
+
+        Ok(ProcessBackend { git_dir, work_tree })
+    }
+
+    fn is_repository(path: &Path) -> Result<bool> {
+        if !git::is_installed() {
+            return Err(Error::GitNotInstalled);
+        }
+
+        let cannon_path = match normalize(path) {
+            Ok(p) => p,
+            Err(Error::NotARepository(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        match run(Path::new(&cannon_path), &[], &["rev-parse"]) {
+            Ok(_) => Ok(true),
+            Err(Error::NotARepository(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn git_dir(&self) -> &Path {
+        &self.git_dir
+    }
+
+    fn work_tree(&self) -> Option<&Path> {
+        self.work_tree.as_deref()
+    }
+
+    fn log(&self, from: &str, to: &str, format: &str, amount: Option<usize>) -> Result<String> {
+        self.run_log(from, to, format, amount, &[])
+    }
+
+    fn commits(
+        &self,
+        from: &str,
+        to: &str,
+        amount: Option<usize>,
+        files: bool,
+    ) -> Result<Vec<CommitRecord>> {
+        let options: &[&str] = if files {
+            &["-z", "--name-status", "-M"]
+        } else {
+            &["-z"]
+        };
+        let output = self.run_log(from, to, COMMIT_FORMAT, amount, options)?;
+
+        // Git separates the commits and the changed files that follow them by NUL as well
+        let mut entries = output
+            .split('\0')
+            .map(|e| e.trim_start_matches('\n'))
+            .peekable();
+        let mut commits = Vec::<CommitRecord>::new();
+
+        while let Some(hash) = entries.next() {
+            if hash.is_empty() {
+                continue;
+            }
+
+            let mut field = || entries.next().unwrap_or_default().to_string();
+            let mut commit = CommitRecord {
+                hash: hash.to_string(),
+                author_name: field(),
+                author_email: field(),
+                timestamp: field().parse().unwrap_or_default(),
+                message: field(),
+                files: Vec::new(),
+            };
+
+            if files {
+                commit.files = name_status(&mut entries);
+            }
+
+            commits.push(commit);
+        }
+
+        Ok(commits)
+    }
+
+    fn tags(&self) -> Result<Vec<String>> {
+        let mut tags = self
+            .git(&["tag"])?
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>();
+
+        tags.sort_unstable();
+        Ok(tags)
+    }
+
+    fn reachable_tags(&self, revision: &str) -> Result<Vec<String>> {
+        let output = match self.git(&["tag", "--merged", revision]) {
+            Ok(o) => o,
+            // Git reports an unknown revision as malformed object name
+            Err(Error::CommandFailed { .. }) if !self.exists(revision) => {
+                return Err(self.unknown_revision(revision.to_string(), &[revision]))
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut tags = output
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<String>>();
+
+        tags.sort_unstable();
+        Ok(tags)
+    }
+
+    fn latest_tag(&self) -> Result<String> {
+        match self.git(&["describe"]) {
+            Ok(tag) => Ok(tag.trim_end().to_string()),
+            Err(Error::NoTagFound) if !self.exists("HEAD") => Err(Error::EmptyRepository),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn commit(&self, message: &str) -> Result<String> {
+        self.git(&["commit", "-m", message])
+    }
+
+    fn is_shallow(&self) -> Result<bool> {
+        Ok(self
+            .git(&["rev-parse", "--is-shallow-repository"])?
+            .trim_end()
+            == "true")
+    }
+
+    fn deepen(&self, depth: usize) -> Result<()> {
+        self.git(&["fetch", "--quiet", &format!("--deepen={}", depth)])?;
+        Ok(())
+    }
+
+    fn current_branch(&self) -> Result<Option<String>> {
+        match self.git(&["symbolic-ref", "--quiet", "--short", "HEAD"]) {
+            Ok(branch) => Ok(Some(branch.trim_end().to_string())),
+            // Exits with 1 without a message if HEAD is detached
+            Err(Error::CommandFailed {
+                status: Some(1), ..
+            }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn head_commit(&self) -> Result<String> {
+        match self.git(&["rev-parse", "--verify", "--quiet", "HEAD^{commit}"]) {
+            Ok(hash) => Ok(hash.trim_end().to_string()),
+            Err(Error::CommandFailed {
+                status: Some(1), ..
+            }) => Err(Error::EmptyRepository),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn status(&self) -> Result<Vec<FileStatus>> {
+        let output = self.git(&["status", "--porcelain=v1", "-z", "--untracked-files=all"])?;
+        let mut entries = output.split('\0').filter(|e| !e.is_empty());
+        let mut files = Vec::<FileStatus>::new();
+
+        // Every entry is `XY path`, X is the staged and Y the unstaged change, renamed and
+        // copied files are followed by an entry with the original path
+        while let Some(entry) = entries.next() {
+            let (Some(code), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+                continue;
+            };
+            let mut file = FileStatus::new(path);
+
+            // a file added with `git add --intent-to-add` is tracked and listed as ` A`
+            if code == "??" {
+                file.unstaged = Some(Change::Added);
+                file.untracked = true;
+            } else {
+                let mut codes = code.chars();
+                let staged = codes.next().unwrap_or(' ');
+                let original = match staged {
+                    'R' | 'C' => entries.next().unwrap_or_default(),
+                    _ => "",
+                };
+
+                file.staged = status_change(staged, original);
+                file.unstaged = status_change(codes.next().unwrap_or(' '), "");
+            }
+
+            files.push(file);
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    fn upstream(&self) -> Result<Option<String>> {
+        match self.git(&[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ]) {
+            Ok(upstream) => Ok(Some(upstream.trim_end().to_string())),
+            Err(Error::CommandFailed { stderr, .. })
+                if stderr.contains("upstream") || stderr.contains("does not point to a branch") =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn ahead_behind(&self, revision: &str) -> Result<(usize, usize)> {
+        let range = format!("HEAD...{}", revision);
+
+        let output = match self.git(&["rev-list", "--left-right", "--count", &range, "--"]) {
+            Ok(o) => o,
+            Err(Error::UnknownRevision(range)) => {
+                return Err(self.unknown_revision(range, &[revision]))
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut counts = output.split_whitespace().map(|c| c.parse::<usize>());
+
+        match (counts.next(), counts.next()) {
+            (Some(Ok(ahead)), Some(Ok(behind))) => Ok((ahead, behind)),
+            _ => Err(Error::CommandFailed {
+                args: vec![String::from("rev-list"), range],
+                status: Some(0),
+                stderr: format!("Unexpected output \"{}\"", output.trim_end()),
+            }),
+        }
+    }
+}
+
+/// Runs git with the arguments and environment variables in the directory and maps the known
+/// failures to an [Error]
+fn run(dir: &Path, envs: &[(&str, &Path)], args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .envs(envs.iter().copied())
+        // The messages of git are matched below, so they must not be translated
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Error::GitNotInstalled,
+            _ => Error::Io(e),
+        })?;
+
+    if output.status.success() {
+        return String::from_utf8(output.stdout).map_err(|_| Error::NonUtf8Output);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    Err(if stderr.contains("not a git repository") {
+        Error::NotARepository(dir.to_path_buf())
+    } else if stderr.contains("does not have any commits yet") {
+        Error::EmptyRepository
+    } else if let Some(revision) = unknown_revision(&stderr) {
+        Error::UnknownRevision(revision)
+    } else if stderr.contains("No names found") || stderr.contains("No annotated tags") {
+        Error::NoTagFound
+    } else {
+        Error::CommandFailed {
+            args: args.iter().map(|a| a.to_string()).collect(),
+            status: output.status.code(),
+            // `git commit` writes why nothing was committed to stdout
+            stderr: if stderr.is_empty() {
+                String::from_utf8_lossy(&output.stdout).into_owned()
+            } else {
+                stderr
+            },
+        }
+    })
+}
+
+/// The change of a status code of `git status --porcelain` or `git log --name-status`,
+/// `original` is the path a renamed or copied file originates from
+fn status_change(code: char, original: &str) -> Option<Change> {
+    match code {
+        'A' => Some(Change::Added),
+        'M' | 'U' => Some(Change::Modified),
+        'D' => Some(Change::Deleted),
+        'R' => Some(Change::Renamed(original.to_string())),
+        'C' => Some(Change::Copied(original.to_string())),
+        'T' => Some(Change::TypeChanged),
+        _ => None,
+    }
+}
+
+/// Parses the changed files that follow a commit in `git log --name-status -z` and sorts them
+/// by path
+///
+/// Every file is a status code followed by its path, for renamed and copied files the
+/// original path comes first. The files end at the hash of the next commit.
+fn name_status<'a>(entries: &mut Peekable<impl Iterator<Item = &'a str>>) -> Vec<FileChange> {
+    let mut files = Vec::<FileChange>::new();
+
+    // A status code is at most a letter and a score, never as long as a hash
+    while let Some(code) = entries.next_if(|e| !e.is_empty() && e.len() <= 4) {
+        let code = code.chars().next().unwrap_or(' ');
+        let original = match code {
+            'R' | 'C' => entries.next().unwrap_or_default(),
+            _ => "",
+        };
+
+        if let (Some(path), Some(change)) = (entries.next(), status_change(code, original)) {
+            files.push(FileChange {
+                path: path.to_string(),
+                change,
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+/// Extracts the revision (or range) from the message git prints if a revision does not exist
+fn unknown_revision(stderr: &str) -> Option<String> {
+    let start = stderr.find("bad revision '")? + "bad revision '".len();
+    let end = stderr[start..].find('\'')? + start;
+
+    Some(stderr[start..end].to_string())
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+/// Runs git in the directory and panics if it fails
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args([
+            "-c",
+            "user.name=cocors",
+            "-c",
+            "user.email=cocors@example.com",
+        ])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed; {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Creates a repository in a temporary directory with a commit for every message
+pub fn repository(messages: &[&str]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "--quiet"]);
+    git(dir.path(), &["config", "user.name", "cocors"]);
+    git(dir.path(), &["config", "user.email", "cocors@example.com"]);
+
+    for (i, message) in messages.iter().enumerate() {
+        fs::write(dir.path().join("file"), i.to_string()).unwrap();
+        git(dir.path(), &["add", "file"]);
+        git(dir.path(), &["commit", "--quiet", "-m", message]);
+    }
+
+    dir
+}
+
+/// Creates a bare clone of the repository in a temporary directory
+pub fn bare_clone(repository: &Path) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "--quiet",
+            "--bare",
+            repository.to_str().unwrap(),
+            ".",
+        ],
+    );
+
+    dir
+}
+
+/// Clones the repository with the given depth into a temporary directory
+pub fn shallow_clone(repository: &Path, depth: usize) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "--quiet",
+            "--depth",
+            &depth.to_string(),
+            &format!("file://{}", repository.display()),
+            ".",
+        ],
+    );
+
+    dir
+}
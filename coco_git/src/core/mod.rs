@@ -1,5 +1,11 @@
+pub mod backend;
+#[cfg(test)]
+mod fixture;
 pub mod git;
 mod repository;
+mod status;
 pub mod utility;
 
+pub use backend::GitBackend;
 pub use repository::Repository;
+pub use status::{Change, FileStatus};
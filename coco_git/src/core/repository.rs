@@ -1,234 +1,988 @@
-use std::io;
+use std::cell::Cell;
 use std::path::Path;
-use std::process::Command;
 
-use super::{git, utility};
+use log::{debug, warn};
+
+use super::backend::{DefaultBackend, GitBackend};
+use super::FileStatus;
+use crate::log::CommitRecord;
+use crate::{Error, Result};
 
 pub struct Repository {
-    path: String,
+    backend: Box<dyn GitBackend>,
+    /// Whether the repository is a shallow clone, checked on first use
+    shallow: Cell<Option<bool>>,
+    /// Whether the incomplete history of a shallow clone has been warned about
+    warned_shallow: Cell<bool>,
 }
 
 impl Repository {
     /// Returns a new Repository with an associated root path from a path, that can be anywhere in the repo hierarchy
     ///
-    /// If the provided path is not in a repository, there is no access to the path or git is not installed
-    /// an [io::Error] will be returned detailling the issue
-    pub fn new(path: &Path) -> Result<Repository, io::Error> {
-        let mut s = Self::repo_root(path)?;
+    /// The repository is accessed with the [DefaultBackend]. If the provided path is not in a repository,
+    /// there is no access to the path or git is not installed an [Error](crate::Error) will be returned detailling the issue
+    pub fn new(path: &Path) -> Result<Repository> {
+        Ok(Self::with_backend(DefaultBackend::open(path)?))
+    }
 
-        while s.ends_with('\n') || s.ends_with('\r') {
-            s.pop();
+    /// Returns a new Repository that runs all operations with the given backend
+    pub fn with_backend(backend: impl GitBackend + 'static) -> Repository {
+        Repository {
+            backend: Box::new(backend),
+            shallow: Cell::new(None),
+            warned_shallow: Cell::new(false),
         }
-        Ok(Repository { path: s })
     }
 
-    pub fn log(
+    /// The root path of the repository, which is the work tree or the git directory of a bare
+    /// repository
+    pub fn path(&self) -> &Path {
+        self.backend.root()
+    }
+
+    /// The directory that contains the objects and references of the repository
+    pub fn git_dir(&self) -> &Path {
+        self.backend.git_dir()
+    }
+
+    /// The directory with the checked out files, `None` if the repository is bare
+    pub fn work_tree(&self) -> Option<&Path> {
+        self.backend.work_tree()
+    }
+
+    /// Queries the history like `git log <from>..<to> --format=<format> -<amount>`
+    ///
+    /// In a shallow clone the history ends at the depth it was fetched with, which is warned about.
+    pub fn log(&self, from: &str, to: &str, format: &str, amount: Option<usize>) -> Result<String> {
+        self.warn_if_shallow()?;
+
+        self.backend.log(from, to, format, amount)
+    }
+
+    /// Queries the history like [Repository::log], but as typed records
+    ///
+    /// If `files` is set, every record lists the files changed by the commit (like
+    /// `git log --name-status -M`), which makes querying the history more expensive.
+    pub fn commits(
         &self,
         from: &str,
         to: &str,
-        format: &str,
         amount: Option<usize>,
-    ) -> io::Result<String> {
-        let mut cmd = Command::new("git");
+        files: bool,
+    ) -> Result<Vec<CommitRecord>> {
+        self.warn_if_shallow()?;
 
-        let mut range = from.to_string();
+        self.backend.commits(from, to, amount, files)
+    }
 
-        if !to.is_empty() {
-            range.push_str(format!("..{}", to).as_str());
-        }
+    /// Queries all tags in the repository and returns them sorted in alphanumerical order [Ord for str](https://doc.rust-lang.org/std/cmp/trait.Ord.html#impl-Ord-15)
+    ///
+    /// Fails if the path in the Repository is not actually a repository
+    pub fn tags(&self) -> Result<Vec<String>> {
+        self.backend.tags()
+    }
 
-        cmd.arg("log");
+    /// Queries the tags pointing to a commit reachable from the revision, e.g. the releases
+    /// contained in HEAD, sorted like [Repository::tags]
+    pub fn reachable_tags(&self, revision: &str) -> Result<Vec<String>> {
+        self.backend.reachable_tags(revision)
+    }
 
-        if !range.is_empty() {
-            cmd.arg(range);
+    /// The most recent annotated tag reachable from HEAD like `git describe`
+    ///
+    /// Fails with [Error::ShallowRepository] if no tag is found in a shallow clone, as the tag may
+    /// not have been fetched.
+    pub fn latest_tag(&self) -> Result<String> {
+        match self.backend.latest_tag() {
+            Err(Error::NoTagFound) if self.is_shallow()? => Err(Error::ShallowRepository),
+            tag => tag,
         }
+    }
 
-        if !format.is_empty() {
-            cmd.arg(format!("--format={}", format));
+    /// Checks if the repository is a shallow clone, which only contains the recent history
+    ///
+    /// The result is cached until the history is deepened with [Repository::deepen].
+    pub fn is_shallow(&self) -> Result<bool> {
+        if let Some(shallow) = self.shallow.get() {
+            return Ok(shallow);
         }
 
-        if amount.is_some() {
-            cmd.arg(format!("-{}", amount.unwrap()));
-        };
-
-        cmd.current_dir(&self.path);
-        let output = cmd.output()?;
+        let shallow = self.backend.is_shallow()?;
+        self.shallow.set(Some(shallow));
+        Ok(shallow)
+    }
 
-        if output.status.success() {
-            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    /// Warns once that the history of a shallow clone may be incomplete
+    fn warn_if_shallow(&self) -> Result<()> {
+        if !self.warned_shallow.get() && self.is_shallow()? {
+            warn!(
+                "The repository is a shallow clone, the history of {} may be incomplete",
+                self.path().display()
+            );
+            self.warned_shallow.set(true);
         }
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "git log command failed with error; {}",
-                String::from_utf8_lossy(&output.stderr).into_owned()
-            ),
-        ));
+
+        Ok(())
     }
 
-    /// Queries all tags in the repository and returns them sorted in alphanumerical order [Ord for str](https://doc.rust-lang.org/std/cmp/trait.Ord.html#impl-Ord-15)
-    ///
-    /// Fails if the path in the Repository is not actually a repository
-    pub fn tags(&self) -> io::Result<Vec<String>> {
-        let mut cmd = Command::new("git");
+    /// Fetches `depth` more commits of the history of a shallow clone from its remote
+    pub fn deepen(&self, depth: usize) -> Result<()> {
+        // the clone is complete if the remote has no more history
+        self.shallow.set(None);
+        self.backend.deepen(depth)
+    }
 
-        cmd.arg("tag");
-        cmd.current_dir(&self.path);
+    /// Deepens a shallow clone by `step` commits at a time until one of the tags reachable from
+    /// HEAD is accepted by `matches`, and returns the reachable tags like
+    /// [Repository::reachable_tags]
+    ///
+    /// Lightweight and annotated tags are both considered. If the complete history does not
+    /// contain a matching tag, the tags reachable from HEAD are returned anyway. Fails with
+    /// [Error::ShallowRepository] if the remote does not provide more history.
+    pub fn deepen_until_tag(
+        &self,
+        step: usize,
+        matches: impl Fn(&str) -> bool,
+    ) -> Result<Vec<String>> {
+        let mut fetched = self.backend.log("HEAD", "", "%h", None)?.lines().count();
+
+        loop {
+            let tags = self.backend.reachable_tags("HEAD")?;
+            if tags.iter().any(|t| matches(t)) || !self.is_shallow()? {
+                return Ok(tags);
+            }
 
-        let output = cmd.output()?;
+            debug!(
+                "No matching tag found in the shallow clone, fetching {} more commits",
+                step
+            );
+            self.deepen(step)?;
 
-        if output.status.success() {
-            let tags_raw = String::from_utf8_lossy(&output.stdout).into_owned();
-            if tags_raw.is_empty() {
-                return Ok(Vec::<String>::new());
+            let count = self.backend.log("HEAD", "", "%h", None)?.lines().count();
+            if count == fetched {
+                return Err(Error::ShallowRepository);
             }
-            let mut tags = tags_raw
-                .split_whitespace()
-                .map(|t| String::from(t.trim_end()))
-                .collect::<Vec<String>>();
-
-            tags.sort_unstable();
-            return Ok(tags);
+            fetched = count;
         }
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "git log command failed with error; {}",
-                String::from_utf8_lossy(&output.stderr).into_owned()
-            ),
-        ));
     }
 
-    pub fn latest_tag(&self) -> io::Result<String> {
-        let mut cmd = Command::new("git");
+    /// The name of the checked out branch, `None` if HEAD is detached
+    pub fn current_branch(&self) -> Result<Option<String>> {
+        self.backend.current_branch()
+    }
+
+    /// The full hash of the commit HEAD points to
+    ///
+    /// Fails with [Error::EmptyRepository] if nothing has been committed yet
+    pub fn head_commit(&self) -> Result<String> {
+        self.backend.head_commit()
+    }
+
+    /// The files with uncommitted changes, including untracked files, sorted by path
+    ///
+    /// A bare repository has no work tree, so no file is changed
+    pub fn status(&self) -> Result<Vec<FileStatus>> {
+        match self.work_tree() {
+            Some(_) => self.backend.status(),
+            None => Ok(Vec::new()),
+        }
+    }
 
-        cmd.arg("describe");
-        cmd.current_dir(&self.path);
+    /// Checks if a tracked file has uncommitted changes, untracked files are not considered
+    pub fn is_dirty(&self) -> Result<bool> {
+        Ok(self.status()?.iter().any(|f| !f.is_untracked()))
+    }
 
-        let output = cmd.output()?;
+    /// The remote tracking branch of the checked out branch (e.g. `origin/main`), `None` if
+    /// HEAD is detached or the branch has no upstream
+    pub fn upstream(&self) -> Result<Option<String>> {
+        self.backend.upstream()
+    }
 
-        if output.status.success() {
-            let s = String::from_utf8_lossy(&output.stdout).into_owned();
+    /// The number of commits HEAD is ahead and behind of the revision (e.g. the upstream)
+    pub fn ahead_behind(&self, revision: &str) -> Result<(usize, usize)> {
+        self.backend.ahead_behind(revision)
+    }
 
-            return Ok(s.trim_end().to_string());
-        }
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "git log command failed with error; {}",
-                String::from_utf8_lossy(&output.stderr).into_owned()
-            ),
-        ));
+    /// Commits the staged changes with the given message and returns the output of git
+    ///
+    /// Fails if there is nothing to commit or a hook (e.g. commit-msg) rejects the commit
+    pub fn commit(&self, message: &str) -> Result<String> {
+        self.backend.commit(message)
     }
 
     /// Checks wether or not the given path (file or directory) is in a repository
     ///
     /// The path can be either a path to a file or a directory and does not need to be
     /// the repo root path, any path descendant of a repository will return true
-    pub fn is_repository(path: &Path) -> Result<bool, io::Error> {
-        if !git::is_installed() {
-            return Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                "The git command could not be found, make sure git is installed and can be found by the system",
-            ));
-        }
+    pub fn is_repository(path: &Path) -> Result<bool> {
+        DefaultBackend::is_repository(path)
+    }
+
+    /// Returns the root path for a repository, which is an ancestor of the given path
+    ///
+    /// The path can be either a file or directory, but must be a descendant of a repository.
+    /// For a bare repository this is the repository itself.
+    pub fn repo_root(path: &Path) -> Result<String> {
+        Ok(DefaultBackend::open(path)?
+            .root()
+            .to_string_lossy()
+            .into_owned())
+    }
+}
 
-        let cannon_path = utility::normalize_pathname(path)?;
-        let mut cmd = Command::new("git");
+#[cfg(test)]
+mod parse_test {
+
+    use crate::core::backend::ProcessBackend;
+    use crate::core::{GitBackend, Repository};
+    use dirs;
+    use std::path::PathBuf;
 
-        cmd.arg("rev-parse").current_dir(cannon_path);
+    #[test]
+    fn check_if_home_is_repo() {
+        let home_path = dirs::home_dir().unwrap();
+
+        assert!(!Repository::is_repository(home_path.as_path()).unwrap());
+    }
+
+    #[test]
+    fn check_if_manifest_dir_is_repo() {
+        let home_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        assert!(Repository::is_repository(home_path.as_path()).unwrap());
+    }
 
-        match cmd.output() {
-            Ok(o) => {
-                if o.status.success() {
-                    return Ok(true);
-                }
-                return Ok(false);
+    #[test]
+    fn check_if_manifest_dir_returns_history() {
+        let manifest_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let repo = Repository::new(&manifest_path).unwrap();
+        let log = repo
+            .log("", "", "%h»¦«%cn»¦«%ce»¦«%ct»¦«%s»¦«%b", Some(5))
+            .unwrap();
+
+        assert_eq!(log.lines().filter(|l| l.contains("»¦«")).count(), 5);
+    }
+
+    #[test]
+    fn process_backend_root() {
+        let manifest_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let backend = ProcessBackend::open(&manifest_path).unwrap();
+
+        assert_eq!(
+            Repository::with_backend(backend).path().to_str().unwrap(),
+            Repository::repo_root(&manifest_path.join("src")).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod error_test {
+
+    use crate::core::backend::ProcessBackend;
+    use crate::core::{fixture, GitBackend};
+    use crate::Error;
+
+    /// Checks that the backend reports each failure with the matching error
+    fn check_errors<B: GitBackend>() {
+        let not_a_repository = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            B::open(not_a_repository.path()),
+            Err(Error::NotARepository(_))
+        ));
+
+        let empty = fixture::repository(&[]);
+        let backend = B::open(empty.path()).unwrap();
+        assert!(matches!(
+            backend.log("HEAD", "", "%s", None),
+            Err(Error::EmptyRepository)
+        ));
+        assert!(matches!(backend.latest_tag(), Err(Error::EmptyRepository)));
+
+        let untagged = fixture::repository(&["feat: first", "fix: second"]);
+        let backend = B::open(untagged.path()).unwrap();
+        assert!(matches!(backend.latest_tag(), Err(Error::NoTagFound)));
+        assert!(matches!(
+            backend.log("v1.0.0", "HEAD", "%s", None),
+            Err(Error::UnknownRevision(r)) if r == "v1.0.0"
+        ));
+        assert!(matches!(
+            backend.log("HEAD", "v1.0.0", "%s", None),
+            Err(Error::UnknownRevision(r)) if r == "v1.0.0"
+        ));
+    }
+
+    #[test]
+    fn process_errors() {
+        check_errors::<ProcessBackend>();
+    }
+
+    #[cfg(feature = "gix")]
+    #[test]
+    fn gix_errors() {
+        check_errors::<crate::core::backend::GixBackend>();
+    }
+
+    #[test]
+    fn command_failed() {
+        let repository = fixture::repository(&["feat: first"]);
+        let backend = ProcessBackend::open(repository.path()).unwrap();
+
+        match backend.commit("fix: nothing staged") {
+            Err(Error::CommandFailed { args, status, .. }) => {
+                assert_eq!(args, ["commit", "-m", "fix: nothing staged"]);
+                assert_eq!(status, Some(1));
             }
-            Err(e) => return Err(e),
+            other => panic!("Expected CommandFailed, got {:?}", other.map(|_| ())),
         }
     }
+}
 
-    /// Returns the root path for a repository, which is an ancestor of the given path
-    ///
-    /// The path can be either a file or directory, but must be a descendant of a repository
-    pub fn repo_root(path: &Path) -> Result<String, io::Error> {
-        let cannon_path = utility::normalize_pathname(path)?;
-        if !Self::is_repository(Path::new(&cannon_path))? {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Given path is not a repository, therefore root can not be determined",
-            ));
-        }
+#[cfg(test)]
+mod layout_test {
+
+    use std::path::Path;
+
+    use crate::core::backend::ProcessBackend;
+    use crate::core::{fixture, GitBackend};
+    use crate::Error;
+
+    fn canonical(path: &Path) -> std::path::PathBuf {
+        dunce::canonicalize(path).unwrap()
+    }
+
+    /// Checks that the backend opens bare repositories, linked worktrees and submodules
+    fn check_layouts<B: GitBackend>() {
+        let repository = fixture::repository(&["feat: first", "fix: second"]);
+        fixture::git(repository.path(), &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+
+        let bare = fixture::bare_clone(repository.path());
+        let backend = B::open(bare.path()).unwrap();
+        assert_eq!(backend.work_tree(), None);
+        assert_eq!(backend.git_dir(), canonical(bare.path()));
+        assert_eq!(backend.root(), canonical(bare.path()));
+        assert_eq!(
+            backend.log("HEAD", "", "%s", None).unwrap(),
+            "fix: second\nfeat: first\n"
+        );
+        assert_eq!(backend.latest_tag().unwrap(), "v1.0.0");
+
+        let worktree = tempfile::tempdir().unwrap();
+        fixture::git(
+            repository.path(),
+            &[
+                "worktree",
+                "add",
+                "--quiet",
+                "--detach",
+                worktree.path().to_str().unwrap(),
+                "HEAD~1",
+            ],
+        );
+        let backend = B::open(&worktree.path().join("file")).unwrap();
+        assert_eq!(
+            backend.work_tree(),
+            Some(canonical(worktree.path()).as_path())
+        );
+        assert!(backend
+            .git_dir()
+            .starts_with(canonical(&repository.path().join(".git/worktrees"))));
+        assert_eq!(
+            backend.log("HEAD", "", "%s", None).unwrap(),
+            "feat: first\n"
+        );
+
+        let submodule = fixture::repository(&["docs: submodule"]);
+        let parent = fixture::repository(&["chore: parent"]);
+        fixture::git(
+            parent.path(),
+            &[
+                "-c",
+                "protocol.file.allow=always",
+                "submodule",
+                "add",
+                "--quiet",
+                submodule.path().to_str().unwrap(),
+                "sub",
+            ],
+        );
+        let backend = B::open(&parent.path().join("sub")).unwrap();
+        assert_eq!(
+            backend.work_tree(),
+            Some(canonical(&parent.path().join("sub")).as_path())
+        );
+        assert!(backend
+            .git_dir()
+            .starts_with(canonical(&parent.path().join(".git/modules"))));
+        assert_eq!(
+            backend.log("HEAD", "", "%s", None).unwrap(),
+            "docs: submodule\n"
+        );
+
+        assert!(matches!(
+            B::open(&repository.path().join("missing")),
+            Err(Error::NotARepository(_))
+        ));
+        assert!(!B::is_repository(&repository.path().join("missing")).unwrap());
+    }
+
+    #[test]
+    fn process_layouts() {
+        check_layouts::<ProcessBackend>();
+    }
+
+    #[cfg(feature = "gix")]
+    #[test]
+    fn gix_layouts() {
+        check_layouts::<crate::core::backend::GixBackend>();
+    }
+}
+
+#[cfg(test)]
+mod shallow_test {
 
-        let mut cmd = Command::new("git");
-        cmd.arg("rev-parse")
-            .arg("--show-toplevel")
-            .current_dir(cannon_path);
+    use crate::core::backend::ProcessBackend;
+    use crate::core::{fixture, GitBackend, Repository};
+    use crate::Error;
+
+    /// Creates a remote with a tagged release followed by three commits and a clone of depth 1
+    fn shallow_clone() -> (tempfile::TempDir, tempfile::TempDir) {
+        let repository =
+            fixture::repository(&["feat: release", "fix: one", "fix: two", "fix: three"]);
+        fixture::git(
+            repository.path(),
+            &["tag", "-a", "v1.0.0", "HEAD~3", "-m", "v1.0.0"],
+        );
+
+        let remote = fixture::bare_clone(repository.path());
+        let clone = fixture::shallow_clone(remote.path(), 1);
+
+        (remote, clone)
+    }
 
-        if cmd.output().is_err() {
-            return Err(cmd.output().err().unwrap());
+    #[test]
+    fn detect_shallow_clone() {
+        let (remote, clone) = shallow_clone();
+
+        assert!(!ProcessBackend::open(remote.path())
+            .unwrap()
+            .is_shallow()
+            .unwrap());
+        assert!(ProcessBackend::open(clone.path())
+            .unwrap()
+            .is_shallow()
+            .unwrap());
+
+        #[cfg(feature = "gix")]
+        {
+            use crate::core::backend::GixBackend;
+            assert!(!GixBackend::open(remote.path())
+                .unwrap()
+                .is_shallow()
+                .unwrap());
+            assert!(GixBackend::open(clone.path())
+                .unwrap()
+                .is_shallow()
+                .unwrap());
         }
+    }
+
+    #[test]
+    fn cache_shallow_check() {
+        let (_remote, clone) = shallow_clone();
+        let repository = Repository::with_backend(ProcessBackend::open(clone.path()).unwrap());
+
+        assert!(repository.is_shallow().unwrap());
+        std::fs::remove_file(clone.path().join(".git/shallow")).unwrap();
+
+        assert!(repository.is_shallow().unwrap());
+        assert!(
+            !Repository::with_backend(ProcessBackend::open(clone.path()).unwrap())
+                .is_shallow()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_tag_in_shallow_clone() {
+        let (_remote, clone) = shallow_clone();
+        let repository = Repository::with_backend(ProcessBackend::open(clone.path()).unwrap());
 
-        let output = cmd.output().unwrap();
+        assert!(matches!(
+            repository.latest_tag(),
+            Err(Error::ShallowRepository)
+        ));
+    }
+
+    /// Checks that a shallow clone is deepened by the given number of commits
+    fn check_deepen<B: GitBackend + 'static>() {
+        let (_remote, clone) = shallow_clone();
+        let repository = Repository::with_backend(B::open(clone.path()).unwrap());
+
+        repository.deepen(2).unwrap();
+
+        assert!(repository.is_shallow().unwrap());
+        assert_eq!(
+            repository.commits("HEAD", "", None, false).unwrap().len(),
+            3
+        );
+
+        repository.deepen(2).unwrap();
+
+        assert!(!repository.is_shallow().unwrap());
+        assert_eq!(
+            repository.latest_tag().unwrap().split('-').next(),
+            Some("v1.0.0")
+        );
+    }
+
+    #[test]
+    fn process_deepen() {
+        check_deepen::<ProcessBackend>();
+    }
+
+    #[cfg(feature = "gix")]
+    #[test]
+    fn gix_deepen() {
+        check_deepen::<crate::core::backend::GixBackend>();
+    }
 
-        if !output.status.success() {
-            return Err(io::Error::new(
-                io::ErrorKind::UnexpectedEof,
-                format!(
-                    "git rev-parse returned non zero exit code; \n {}",
-                    String::from_utf8_lossy(&output.stderr)
-                ),
-            ));
+    #[test]
+    fn deepen_until_tag() {
+        let (_remote, clone) = shallow_clone();
+        let repository = Repository::with_backend(ProcessBackend::open(clone.path()).unwrap());
+
+        assert_eq!(
+            repository
+                .deepen_until_tag(1, |t| t.starts_with('v'))
+                .unwrap(),
+            vec![String::from("v1.0.0")]
+        );
+        assert!(repository.is_shallow().unwrap());
+        assert_eq!(
+            repository
+                .log("HEAD", "", "%s", None)
+                .unwrap()
+                .lines()
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn deepen_until_matching_tag() {
+        let repository =
+            fixture::repository(&["feat: release", "fix: one", "fix: two", "fix: three"]);
+        fixture::git(repository.path(), &["tag", "v1.0.0", "HEAD~3"]);
+        fixture::git(
+            repository.path(),
+            &["tag", "-a", "reviewed", "HEAD~1", "-m", "reviewed"],
+        );
+
+        let remote = fixture::bare_clone(repository.path());
+        let clone = fixture::shallow_clone(remote.path(), 1);
+        let repository = Repository::with_backend(ProcessBackend::open(clone.path()).unwrap());
+
+        assert_eq!(
+            repository
+                .deepen_until_tag(1, |t| t.starts_with('v'))
+                .unwrap(),
+            vec![String::from("reviewed"), String::from("v1.0.0")]
+        );
+        assert!(repository.is_shallow().unwrap());
+    }
+
+    #[test]
+    fn deepen_without_tag() {
+        let repository = fixture::repository(&["feat: first", "fix: second"]);
+        let remote = fixture::bare_clone(repository.path());
+        let clone = fixture::shallow_clone(remote.path(), 1);
+        let repository = Repository::with_backend(ProcessBackend::open(clone.path()).unwrap());
+
+        assert!(repository.deepen_until_tag(5, |_| true).unwrap().is_empty());
+        assert!(!repository.is_shallow().unwrap());
+    }
+}
+
+#[cfg(test)]
+mod status_test {
+
+    use std::fs;
+
+    use crate::core::backend::ProcessBackend;
+    use crate::core::{fixture, Change, FileStatus, GitBackend, Repository};
+    use crate::Error;
+
+    fn status(path: &str, staged: Option<Change>, unstaged: Option<Change>) -> FileStatus {
+        FileStatus {
+            path: path.to_string(),
+            staged,
+            unstaged,
+            untracked: false,
         }
+    }
 
-        return match String::from_utf8(output.stdout) {
-            Ok(p) => {
-                return Ok(p);
-            }
-            ,
-            Err(e) => {
-                Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Returned data in stdout contained non-UTF8 characters which is not supported, {}",
-                e),
-            ))
-            }
-        };
+    fn untracked(path: &str) -> FileStatus {
+        FileStatus {
+            untracked: true,
+            ..status(path, None, Some(Change::Added))
+        }
+    }
+
+    /// Checks the branch, status and upstream queries against a clone of a bare remote
+    fn check_status<B: GitBackend + 'static>() {
+        let origin = fixture::repository(&["feat: first", "fix: second"]);
+        fs::write(origin.path().join("a.txt"), "a file that will be renamed\n").unwrap();
+        fixture::git(origin.path(), &["add", "a.txt"]);
+        fixture::git(origin.path(), &["commit", "--quiet", "-m", "docs: third"]);
+        fixture::git(origin.path(), &["checkout", "--quiet", "-b", "topic"]);
+
+        let remote = fixture::bare_clone(origin.path());
+        let clone = tempfile::tempdir().unwrap();
+        fixture::git(
+            clone.path(),
+            &["clone", "--quiet", remote.path().to_str().unwrap(), "."],
+        );
+        let repository = Repository::with_backend(B::open(clone.path()).unwrap());
+
+        assert_eq!(
+            repository.current_branch().unwrap().as_deref(),
+            Some("topic")
+        );
+        assert_eq!(
+            repository.head_commit().unwrap(),
+            fixture::git(clone.path(), &["rev-parse", "HEAD"]).trim_end()
+        );
+        assert_eq!(
+            repository.upstream().unwrap().as_deref(),
+            Some("origin/topic")
+        );
+        assert_eq!(repository.ahead_behind("origin/topic").unwrap(), (0, 0));
+        assert!(repository.status().unwrap().is_empty());
+        assert!(!repository.is_dirty().unwrap());
+
+        fs::create_dir(clone.path().join("dir")).unwrap();
+        fs::write(clone.path().join("dir/untracked"), "untracked").unwrap();
+        assert!(!repository.is_dirty().unwrap());
+
+        fs::write(clone.path().join("file"), "modified").unwrap();
+        fs::write(clone.path().join("new"), "new").unwrap();
+        fixture::git(clone.path(), &["add", "new"]);
+        fs::write(clone.path().join("new"), "modified after staging").unwrap();
+        fixture::git(clone.path(), &["mv", "a.txt", "b.txt"]);
+
+        assert_eq!(
+            repository.status().unwrap(),
+            vec![
+                status("b.txt", Some(Change::Renamed(String::from("a.txt"))), None),
+                untracked("dir/untracked"),
+                status("file", None, Some(Change::Modified)),
+                status("new", Some(Change::Added), Some(Change::Modified)),
+            ]
+        );
+        assert!(repository.is_dirty().unwrap());
+
+        fixture::git(clone.path(), &["reset", "--quiet", "--hard", "HEAD~1"]);
+        fixture::git(
+            clone.path(),
+            &["commit", "--quiet", "--allow-empty", "-m", "ci: local"],
+        );
+        assert_eq!(repository.ahead_behind("origin/topic").unwrap(), (1, 1));
+        assert!(matches!(
+            repository.ahead_behind("missing"),
+            Err(Error::UnknownRevision(r)) if r == "missing"
+        ));
+
+        fixture::git(clone.path(), &["checkout", "--quiet", "-b", "local"]);
+        assert_eq!(repository.upstream().unwrap(), None);
+
+        fixture::git(clone.path(), &["checkout", "--quiet", "--detach"]);
+        assert_eq!(repository.current_branch().unwrap(), None);
+        assert_eq!(repository.upstream().unwrap(), None);
+
+        let repository = Repository::with_backend(B::open(remote.path()).unwrap());
+        assert!(repository.status().unwrap().is_empty());
+
+        let empty = fixture::repository(&[]);
+        let repository = Repository::with_backend(B::open(empty.path()).unwrap());
+        assert!(matches!(
+            repository.head_commit(),
+            Err(Error::EmptyRepository)
+        ));
+    }
+
+    /// Checks that a file added with `git add -N` is reported like git does, as a file added in
+    /// the work tree that is tracked but not staged
+    fn check_intent_to_add<B: GitBackend + 'static>() {
+        let dir = fixture::repository(&["feat: first"]);
+        fs::write(dir.path().join("intent"), "intent to add").unwrap();
+        fixture::git(dir.path(), &["add", "--intent-to-add", "intent"]);
+        let repository = Repository::with_backend(B::open(dir.path()).unwrap());
+
+        let files = repository.status().unwrap();
+        assert_eq!(files, vec![status("intent", None, Some(Change::Added))]);
+        assert!(!files[0].is_untracked());
+        assert!(repository.is_dirty().unwrap());
+
+        fs::write(dir.path().join("untracked"), "untracked").unwrap();
+        assert!(repository.status().unwrap()[1].is_untracked());
+    }
+
+    #[test]
+    fn process_status() {
+        check_status::<ProcessBackend>();
+    }
+
+    #[test]
+    fn process_intent_to_add() {
+        check_intent_to_add::<ProcessBackend>();
+    }
+
+    #[cfg(feature = "gix")]
+    #[test]
+    fn gix_intent_to_add() {
+        check_intent_to_add::<crate::core::backend::GixBackend>();
+    }
+
+    #[cfg(feature = "gix")]
+    #[test]
+    fn gix_status() {
+        check_status::<crate::core::backend::GixBackend>();
     }
 }
 
 #[cfg(test)]
-mod parse_test {
+mod commits_test {
 
-    use crate::core::{utility, Repository};
-    use dirs;
-    use std::path::PathBuf;
+    use std::fs;
+
+    use crate::core::backend::ProcessBackend;
+    use crate::core::{fixture, Change, GitBackend, Repository};
+    use crate::log::{CommitRecord, FileChange};
+    use crate::Error;
+
+    fn file(path: &str, change: Change) -> FileChange {
+        FileChange {
+            path: path.to_string(),
+            change,
+        }
+    }
+
+    /// The record with the message, commits of the same second are not ordered reliably
+    fn find<'a>(records: &'a [CommitRecord], message: &str) -> &'a CommitRecord {
+        records
+            .iter()
+            .find(|r| r.message.trim_end() == message)
+            .unwrap()
+    }
+
+    /// Checks the records and changed files of additions, renames, deletions and merges
+    fn check_commits<B: GitBackend + 'static>() {
+        let dir = fixture::repository(&["feat: first"]);
+        let path = dir.path();
+
+        fs::create_dir_all(path.join("src")).unwrap();
+        fs::create_dir_all(path.join("docs")).unwrap();
+        fs::write(
+            path.join("src/a.rs"),
+            "fn main() {\n    println!(\"a\");\n}\n",
+        )
+        .unwrap();
+        fs::write(path.join("docs/readme"), "readme").unwrap();
+        fixture::git(path, &["add", "."]);
+        fixture::git(path, &["commit", "--quiet", "-m", "feat(src): add"]);
+
+        fixture::git(path, &["mv", "src/a.rs", "src/b.rs"]);
+        fs::write(path.join("file"), "changed").unwrap();
+        fixture::git(
+            path,
+            &["commit", "--quiet", "-am", "refactor: move\n\nbody"],
+        );
+
+        fixture::git(path, &["rm", "--quiet", "docs/readme"]);
+        fixture::git(path, &["commit", "--quiet", "-m", "docs: remove"]);
+
+        fixture::git(path, &["checkout", "--quiet", "-b", "side"]);
+        fs::write(path.join("side"), "side").unwrap();
+        fixture::git(path, &["add", "side"]);
+        fixture::git(path, &["commit", "--quiet", "-m", "feat: side"]);
+        fixture::git(path, &["checkout", "--quiet", "-"]);
+        fixture::git(
+            path,
+            &["merge", "--quiet", "--no-ff", "-m", "chore: merge", "side"],
+        );
+
+        let repository = Repository::with_backend(B::open(path).unwrap());
+        let records = repository.commits("HEAD", "", None, true).unwrap();
+
+        assert_eq!(records.len(), 6);
+        assert_eq!(
+            records[0].hash,
+            fixture::git(path, &["rev-parse", "HEAD"]).trim_end()
+        );
+        assert!(records[0].files.is_empty());
+
+        let first = find(&records, "feat: first");
+        assert_eq!(first.author_name, "cocors");
+        assert_eq!(first.author_email, "cocors@example.com");
+        assert!(first.timestamp > 0);
+        assert_eq!(first.files, vec![file("file", Change::Added)]);
+
+        assert_eq!(
+            find(&records, "feat(src): add").files,
+            vec![
+                file("docs/readme", Change::Added),
+                file("src/a.rs", Change::Added)
+            ]
+        );
+
+        let moved = find(&records, "refactor: move\n\nbody");
+        assert_eq!(
+            moved.files,
+            vec![
+                file("file", Change::Modified),
+                file("src/b.rs", Change::Renamed(String::from("src/a.rs")))
+            ]
+        );
+        assert_eq!(moved.top_level_dirs(), vec!["src"]);
+        assert!(moved.touches("src/a.rs"));
+
+        assert_eq!(
+            find(&records, "docs: remove").files,
+            vec![file("docs/readme", Change::Deleted)]
+        );
+        assert_eq!(
+            find(&records, "feat: side").files,
+            vec![file("side", Change::Added)]
+        );
+
+        fixture::git(path, &["tag", "-a", "v1.0.0", "-m", "v1.0.0", "HEAD~2"]);
+        fixture::git(path, &["tag", "v1.1.0-beta.1", "side"]);
+        fixture::git(path, &["tag", "tree", "HEAD^{tree}"]);
+        assert_eq!(repository.reachable_tags("HEAD~1").unwrap(), vec!["v1.0.0"]);
+        assert_eq!(
+            repository.reachable_tags("HEAD").unwrap(),
+            vec!["v1.0.0", "v1.1.0-beta.1"]
+        );
+        assert!(matches!(
+            repository.reachable_tags("missing"),
+            Err(Error::UnknownRevision(r)) if r == "missing"
+        ));
+
+        let records = repository.commits("HEAD~2", "HEAD~1", None, false).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].message.trim_end(), "docs: remove");
+        assert!(records[0].files.is_empty());
+
+        // Control characters in the message do not split the details of the commit
+        fs::write(path.join("file"), "control").unwrap();
+        fixture::git(
+            path,
+            &["commit", "--quiet", "-am", "fix: a\u{1f}b\n\nM\u{1e}\nR100"],
+        );
+        let records = repository.commits("HEAD", "", Some(2), true).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0].message.trim_end(),
+            "fix: a\u{1f}b\n\nM\u{1e}\nR100"
+        );
+        assert_eq!(records[0].files, vec![file("file", Change::Modified)]);
+        assert_eq!(records[1].message.trim_end(), "chore: merge");
+    }
+
+    /// Checks that the staged changes are committed with the message
+    fn check_commit<B: GitBackend + 'static>() {
+        let dir = fixture::repository(&["feat: first"]);
+        let path = dir.path();
+
+        fs::write(path.join("file"), "changed").unwrap();
+        fixture::git(path, &["add", "file"]);
+
+        let repository = Repository::with_backend(B::open(path).unwrap());
+        repository.commit("fix: change file").unwrap();
+
+        let records = repository.commits("HEAD", "", Some(1), true).unwrap();
+        assert_eq!(records[0].message.trim_end(), "fix: change file");
+        assert_eq!(records[0].files, vec![file("file", Change::Modified)]);
+        assert!(matches!(
+            repository.commit("fix: nothing"),
+            Err(Error::CommandFailed { .. })
+        ));
+    }
 
     #[test]
-    fn check_if_home_is_repo() {
-        let home_path = dirs::home_dir().unwrap();
+    fn process_commits() {
+        check_commits::<ProcessBackend>();
+    }
 
-        assert!(!Repository::is_repository(&home_path.as_path()).unwrap());
+    #[test]
+    fn process_commit() {
+        check_commit::<ProcessBackend>();
     }
 
+    #[cfg(feature = "gix")]
     #[test]
-    fn check_if_manifest_dir_is_repo() {
-        let home_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        assert!(Repository::is_repository(&home_path.as_path()).unwrap());
+    fn gix_commit() {
+        check_commit::<crate::core::backend::GixBackend>();
     }
 
+    #[cfg(feature = "gix")]
     #[test]
-    fn check_if_manifest_dir_returns_history() {
-        let manifest_path = PathBuf::from(r"C:\Users\z003yw7n\Desktop\MISC\LearnGit\LearnGitRepo");
-        let root = utility::normalize_pathname(&manifest_path).unwrap();
-        println!("{}", root);
-        let repo = Repository::new(&manifest_path).unwrap();
-        println!("{}", repo.path.display());
-        let log = repo.log("", "", "%h»¦«%cn»¦«%ce»¦«%ct»¦«%s»¦«%b").unwrap();
+    fn gix_commits() {
+        check_commits::<crate::core::backend::GixBackend>();
+    }
+}
+
+#[cfg(all(test, feature = "gix"))]
+mod gix_test {
+
+    use crate::core::backend::{GixBackend, ProcessBackend};
+    use crate::core::GitBackend;
+    use std::path::PathBuf;
+
+    fn backends() -> (ProcessBackend, GixBackend) {
+        let manifest_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+        (
+            ProcessBackend::open(&manifest_path).unwrap(),
+            GixBackend::open(&manifest_path).unwrap(),
+        )
+    }
+
+    #[test]
+    fn same_root() {
+        let (process, gix) = backends();
+        assert_eq!(process.root(), gix.root());
+    }
 
-        println!("{}", log);
+    #[test]
+    fn same_log() {
+        let (process, gix) = backends();
+        let format = "%H»¦«%h»¦«%an»¦«%ae»¦«%at»¦«%cn»¦«%ce»¦«%ct»¦«%s»¦«%b»¦«%B»»»%n%%%x";
+
+        assert_eq!(
+            process.log("HEAD", "", format, Some(20)).unwrap(),
+            gix.log("HEAD", "", format, Some(20)).unwrap()
+        );
+        assert_eq!(
+            process.log("HEAD~3", "HEAD", format, None).unwrap(),
+            gix.log("HEAD~3", "HEAD", format, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn same_commits() {
+        let (process, gix) = backends();
 
-        assert!(!log.is_empty());
+        assert_eq!(
+            process.commits("HEAD", "", Some(20), true).unwrap(),
+            gix.commits("HEAD", "", Some(20), true).unwrap()
+        );
+    }
+
+    #[test]
+    fn same_tags() {
+        let (process, gix) = backends();
+        assert_eq!(process.tags().unwrap(), gix.tags().unwrap());
+        assert_eq!(process.latest_tag().ok(), gix.latest_tag().ok());
     }
 }
@@ -0,0 +1,44 @@
+/// How a file was changed
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Change {
+    Added,
+    Modified,
+    Deleted,
+    /// The file was moved, with the path it was moved from
+    Renamed(String),
+    /// The file was copied, with the path of the original file
+    Copied(String),
+    /// The type of the file changed, e.g. from a regular file to a symbolic link
+    TypeChanged,
+}
+
+/// The uncommitted changes of a file in the work tree
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct FileStatus {
+    /// The path relative to the root of the work tree
+    pub path: String,
+    /// The change in the index compared to HEAD, which is committed next
+    pub staged: Option<Change>,
+    /// The change in the work tree compared to the index, untracked files and files added with
+    /// `git add --intent-to-add` are [Change::Added]
+    pub unstaged: Option<Change>,
+    /// The file is not in the index, unlike a file added with `git add --intent-to-add`
+    pub untracked: bool,
+}
+
+impl FileStatus {
+    /// Creates the status of a file without any changes, which are set afterwards
+    pub(crate) fn new(path: &str) -> FileStatus {
+        FileStatus {
+            path: path.to_string(),
+            staged: None,
+            unstaged: None,
+            untracked: false,
+        }
+    }
+
+    /// Checks if the file is not tracked by git, which means it is only added in the work tree
+    pub fn is_untracked(&self) -> bool {
+        self.untracked
+    }
+}
@@ -10,13 +10,17 @@ pub fn normalize_pathname(path: &Path) -> Result<String, io::Error> {
             None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Path given was file and parent is either root or resolves in prefix, enter valid directory path"))
         }
     }
-    // Should not panic as the check if the directory exists is already done above
-    let cannon_path = match dunce::canonicalize(dir_path).unwrap().to_str() {
-            Some(s) => s.to_string(),
-            None => return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Pathname does not exist, contains invalid UTF8 or is too long, make sure that the pathname is valid. Non UTF8 characters are not supported"))
-        };
+    let cannon_path = match dunce::canonicalize(&dir_path) {
+        Ok(p) => p,
+        // Directories that can not be resolved (e.g. a parent is not readable) are used as they are
+        Err(_) if dir_path.is_dir() => std::path::absolute(&dir_path)?,
+        Err(e) => return Err(e),
+    };
 
-    Ok(cannon_path)
+    match cannon_path.to_str() {
+        Some(s) => Ok(s.to_string()),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Pathname contains invalid UTF8, make sure that the pathname is valid. Non UTF8 characters are not supported")),
+    }
 }
@@ -0,0 +1,97 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// The result of an operation on a repository
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The reasons an operation on a repository can fail
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The `git` binary could not be found by the system
+    GitNotInstalled,
+    /// The path is not inside a repository
+    NotARepository(PathBuf),
+    /// A revision (e.g. a tag or `HEAD~3`) does not exist in the repository
+    UnknownRevision(String),
+    /// The repository does not contain any commits yet
+    EmptyRepository,
+    /// No annotated tag is reachable from HEAD
+    NoTagFound,
+    /// The repository is a shallow clone and the fetched history does not contain what was
+    /// asked for, see [Repository::deepen_until_tag](crate::core::Repository::deepen_until_tag)
+    ShallowRepository,
+    /// Git returned output that is not valid UTF-8
+    NonUtf8Output,
+    /// A git command exited with a non zero exit code, `status` is `None` if it was killed
+    /// by a signal
+    CommandFailed {
+        args: Vec<String>,
+        status: Option<i32>,
+        stderr: String,
+    },
+    /// Accessing the file system failed
+    Io(io::Error),
+    /// Any other error of the backend
+    Backend(Box<dyn error::Error + Send + Sync>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::GitNotInstalled => write!(
+                f,
+                "The git command could not be found, make sure git is installed and can be found by the system"
+            ),
+            Error::NotARepository(path) => {
+                write!(f, "\"{}\" is not in a repository", path.to_string_lossy())
+            }
+            Error::UnknownRevision(revision) => write!(
+                f,
+                "The revision \"{}\" does not exist in the repository",
+                revision
+            ),
+            Error::EmptyRepository => write!(f, "The repository does not contain any commits"),
+            Error::NoTagFound => write!(f, "No annotated tag is reachable from HEAD"),
+            Error::ShallowRepository => write!(
+                f,
+                "The repository is a shallow clone and its history does not contain a tag, fetch more history with `git fetch --deepen=<depth>` or `git fetch --unshallow`"
+            ),
+            Error::NonUtf8Output => write!(
+                f,
+                "Git returned non-UTF8 characters, which are not supported"
+            ),
+            Error::CommandFailed {
+                args,
+                status,
+                stderr,
+            } => write!(
+                f,
+                "git {} failed with exit code {}; {}",
+                args.join(" "),
+                status.map_or_else(|| String::from("none"), |s| s.to_string()),
+                stderr.trim_end()
+            ),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Backend(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Backend(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
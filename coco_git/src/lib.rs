@@ -1,7 +1,8 @@
 pub mod core;
+mod error;
 pub mod log;
 
-
+pub use error::{Error, Result};
 
 #[cfg(test)]
 mod tests {
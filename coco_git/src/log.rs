@@ -0,0 +1,107 @@
+use crate::core::Change;
+
+/// A commit in the history of a repository
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct CommitRecord {
+    /// The full hash of the commit
+    pub hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// The time the commit was authored, in seconds since the Unix epoch
+    pub timestamp: i64,
+    /// The complete commit message, subject and body
+    pub message: String,
+    /// The files changed compared to the first parent, renames are detected like `git log -M`
+    ///
+    /// Only filled if requested, sorted by path and empty for merge commits like the output of
+    /// `git log`.
+    pub files: Vec<FileChange>,
+}
+
+/// A file changed by a commit
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct FileChange {
+    /// The path relative to the root of the repository, after the change
+    pub path: String,
+    pub change: Change,
+}
+
+impl CommitRecord {
+    /// The top-level directories of the changed files, sorted and without duplicates
+    ///
+    /// Files in the root of the repository are left out.
+    pub fn top_level_dirs(&self) -> Vec<&str> {
+        let mut dirs: Vec<&str> = self
+            .files
+            .iter()
+            .filter_map(|f| f.path.split_once('/').map(|(dir, _)| dir))
+            .collect();
+
+        dirs.sort_unstable();
+        dirs.dedup();
+        dirs
+    }
+
+    /// Checks if the commit changed a file at the path or below it, if it is a directory
+    ///
+    /// The original path of a renamed file also counts as changed.
+    pub fn touches(&self, path: &str) -> bool {
+        let path = path.trim_end_matches('/');
+        let matches = |p: &str| p == path || p.starts_with(&format!("{}/", path));
+
+        self.files.iter().any(|f| {
+            matches(&f.path) || matches!(&f.change, Change::Renamed(from) if matches(from))
+        })
+    }
+}
+
+#[cfg(test)]
+mod log_test {
+
+    use super::{CommitRecord, FileChange};
+    use crate::core::Change;
+
+    fn record(files: &[(&str, Change)]) -> CommitRecord {
+        CommitRecord {
+            hash: String::from("0123456789abcdef"),
+            author_name: String::from("cocors"),
+            author_email: String::from("cocors@example.com"),
+            timestamp: 0,
+            message: String::from("feat: record"),
+            files: files
+                .iter()
+                .map(|(path, change)| FileChange {
+                    path: path.to_string(),
+                    change: change.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn top_level_dirs() {
+        let record = record(&[
+            ("README.md", Change::Modified),
+            ("parser/src/lib.rs", Change::Modified),
+            ("cli/main.rs", Change::Added),
+            ("parser/Cargo.toml", Change::Modified),
+        ]);
+
+        assert_eq!(record.top_level_dirs(), vec!["cli", "parser"]);
+    }
+
+    #[test]
+    fn touches() {
+        let record = record(&[
+            ("parser/src/lib.rs", Change::Modified),
+            ("new/main.rs", Change::Renamed(String::from("old/main.rs"))),
+        ]);
+
+        assert!(record.touches("parser"));
+        assert!(record.touches("parser/"));
+        assert!(record.touches("parser/src/lib.rs"));
+        assert!(record.touches("old"));
+        assert!(!record.touches("pars"));
+        assert!(!record.touches("cli"));
+    }
+}
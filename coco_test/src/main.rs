@@ -17,6 +17,7 @@ fn main() {
             "HEAD",
             repo.latest_tag().unwrap().as_str(),
             "%h»¦«%cn»¦«%ce»¦«%ct»¦«%s»¦«%b",
+            None,
         )
         .unwrap()
         .lines()
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 
 use super::commands::Commands;
@@ -17,6 +19,10 @@ pub struct Args {
     #[clap(short, long)]
     pub verbose: bool,
 
+    /// Path to the configuration file, defaults to `.cocors.yaml` in the current directory
+    #[clap(long, value_parser, global = true)]
+    pub config: Option<PathBuf>,
+
     #[clap(subcommand)]
     pub command: Commands,
 }
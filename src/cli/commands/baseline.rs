@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reads the hashes of the commits in a baseline file, empty lines and comments are skipped
+///
+/// Fails if a line is not a full commit hash, as it would never match a linted commit.
+pub(crate) fn read(path: &Path) -> io::Result<HashSet<String>> {
+    fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            if is_hash(l) {
+                Ok(String::from(l))
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("\"{}\" is not a commit hash", l),
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Writes the hashes of the commits to a baseline file, one hash per line
+pub(crate) fn write(path: &Path, hashes: &[String]) -> io::Result<()> {
+    let mut content = String::from(
+        "# Commits that failed `cocors lint` when this baseline was written, they are not\n\
+         # reported as failures when linting with `--baseline`\n",
+    );
+
+    for hash in hashes {
+        content.push_str(hash);
+        content.push('\n');
+    }
+
+    fs::write(path, content)
+}
+
+/// Checks if the text is a full SHA-1 or SHA-256 commit hash
+fn is_hash(text: &str) -> bool {
+    (text.len() == 40 || text.len() == 64) && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod baseline_test {
+
+    use std::collections::HashSet;
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    use super::{read, write};
+
+    const HASH: &str = "0c9e1aa0c9e1aa0c9e1aa0c9e1aa0c9e1aa0c9e1";
+
+    #[test]
+    fn write_and_read() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.child("baseline");
+
+        write(file.path(), &[String::from(HASH)]).unwrap();
+
+        assert_eq!(
+            read(file.path()).unwrap(),
+            HashSet::from([String::from(HASH)])
+        );
+    }
+
+    #[test]
+    fn skip_comments() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.child("baseline");
+        file.write_str(&format!("# known failures\n\n  {}  \n", HASH))
+            .unwrap();
+
+        assert_eq!(
+            read(file.path()).unwrap(),
+            HashSet::from([String::from(HASH)])
+        );
+    }
+
+    #[test]
+    fn corrupt_baseline() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.child("baseline");
+        file.write_str(&format!("{}\nfeat: add parser\n", HASH))
+            .unwrap();
+
+        assert_eq!(
+            read(file.path()).unwrap_err().to_string(),
+            "\"feat: add parser\" is not a commit hash"
+        );
+        assert!(read(dir.child("missing").path()).is_err());
+    }
+}
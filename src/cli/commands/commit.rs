@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+
+use clap::Args;
+use coco::{parser, CommitType};
+use coco_git::core::Repository;
+use dialoguer::{console, theme::ColorfulTheme, Confirm, Input, Select};
+use log::{error, info, trace};
+
+use super::lint::print_lint_result;
+use super::{git_exit_code, INVALID_INPUT, VIOLATIONS_FOUND};
+use crate::config::Config;
+
+/// Number of commits in the history that are searched for recently used scopes
+const SCOPE_HISTORY: usize = 200;
+
+#[derive(Args)]
+/// Writes a conventional commit message and commits the staged changes
+///
+/// Prompts for every part of the message that is not given as an option, if the type and
+/// the description are given no prompts are shown at all.
+pub struct Commit {
+    /// The type of the commit (e.g. feat or fix)
+    #[clap(short = 't', long = "type")]
+    pub commit_type: Option<String>,
+
+    /// The scope of the commit, multiple scopes are separated by a comma
+    #[clap(short, long)]
+    pub scope: Option<String>,
+
+    /// The short description of the changes
+    #[clap(short = 'm', long)]
+    pub description: Option<String>,
+
+    /// The body of the commit message
+    #[clap(short, long)]
+    pub body: Option<String>,
+
+    /// Marks the commit as breaking change, with a note describing the breaking change
+    #[clap(long, value_name = "NOTE")]
+    pub breaking: Option<String>,
+
+    /// The issues the commit refers to (e.g. "#12, #14")
+    #[clap(short, long)]
+    pub refs: Option<String>,
+
+    /// The path to the repository, defaults to the current directory
+    #[clap(short, long, value_parser)]
+    pub path: Option<PathBuf>,
+
+    /// Prints the message instead of committing
+    #[clap(long)]
+    pub dry_run: bool,
+}
+
+impl Commit {
+    pub fn commit(&self, config: &Config) {
+        trace!("Starting commit functionality");
+
+        let path = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+        let repository = match Repository::new(path.as_path()) {
+            Ok(r) => r,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(git_exit_code(&e));
+            }
+        };
+
+        let root = PathBuf::from(repository.path());
+        let rules = match config.rules(&root) {
+            Ok(r) => r,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(INVALID_INPUT);
+            }
+        };
+
+        let interactive = self.commit_type.is_none() || self.description.is_none();
+
+        if interactive && !console::user_attended_stderr() {
+            error!("No terminal to prompt in, provide at least --type and --description");
+            std::process::exit(exitcode::USAGE);
+        }
+        let theme = ColorfulTheme::default();
+
+        let commit_type = match &self.commit_type {
+            Some(t) => t.clone(),
+            None => {
+                let mut types = type_choices(&config.types);
+
+                let items: Vec<String> = types
+                    .iter()
+                    .map(|t| format!("{:<10} {}", t, t.description()))
+                    .collect();
+                let selected = prompt(
+                    Select::with_theme(&theme)
+                        .with_prompt("Type of the change")
+                        .items(&items)
+                        .default(0)
+                        .interact(),
+                );
+
+                types.swap_remove(selected).to_string()
+            }
+        };
+
+        let scope = match &self.scope {
+            Some(s) => s.clone(),
+            None if interactive => {
+                let mut scopes = recent_scopes(&repository);
+                if let Some(rule) = &rules.scope_enum {
+                    let allowed: Vec<String> = rule
+                        .value
+                        .iter()
+                        .filter(|s| !scopes.contains(s))
+                        .cloned()
+                        .collect();
+                    scopes.extend(allowed);
+                }
+
+                select_scope(&theme, scopes)
+            }
+            None => String::new(),
+        };
+
+        let description = match &self.description {
+            Some(d) => d.clone(),
+            None => prompt(
+                Input::<String>::with_theme(&theme)
+                    .with_prompt("Short description in imperative mood")
+                    .validate_with(|d: &String| {
+                        if d.trim().is_empty() {
+                            Err("The description is mandatory")
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .interact_text(),
+            ),
+        };
+
+        let body = match &self.body {
+            Some(b) => b.clone(),
+            None if interactive => optional_input(&theme, "Body (optional)"),
+            None => String::new(),
+        };
+
+        let breaking = match &self.breaking {
+            Some(b) => Some(b.clone()),
+            None if interactive => prompt(
+                Confirm::with_theme(&theme)
+                    .with_prompt("Is this a breaking change?")
+                    .default(false)
+                    .interact(),
+            )
+            .then(|| optional_input(&theme, "Describe the breaking change (optional)")),
+            None => None,
+        };
+
+        let refs = match &self.refs {
+            Some(r) => r.clone(),
+            None if interactive => optional_input(&theme, "Referenced issues (optional)"),
+            None => String::new(),
+        };
+
+        let message = format_message(
+            &commit_type,
+            &scope,
+            &description,
+            &body,
+            breaking.as_deref(),
+            &refs,
+        );
+        trace!("Composed commit message: {}", message);
+
+        let lint_result = coco::Commit::lint_with(&message, &rules);
+        let valid = lint_result.is_valid();
+
+        if !lint_result.lints.is_empty() {
+            print_lint_result(lint_result, None, &message, false);
+        }
+
+        if !valid {
+            error!("The commit message is not valid, nothing has been committed");
+            std::process::exit(VIOLATIONS_FOUND);
+        }
+
+        if self.dry_run {
+            println!("{}", message);
+            return;
+        }
+
+        if interactive {
+            println!("\n{}\n", message);
+
+            let confirmed = prompt(
+                Confirm::with_theme(&theme)
+                    .with_prompt("Commit with this message?")
+                    .default(true)
+                    .interact(),
+            );
+            if !confirmed {
+                info!("Nothing has been committed");
+                return;
+            }
+        }
+
+        match repository.commit(&message) {
+            Ok(output) => info!("{}", output.trim_end()),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(git_exit_code(&e));
+            }
+        }
+    }
+}
+
+/// The types offered for selection, the conventional types followed by the custom types
+/// of the configuration
+fn type_choices(custom: &[String]) -> Vec<CommitType> {
+    let mut types = CommitType::CONVENTIONAL.to_vec();
+    types.extend(
+        custom
+            .iter()
+            .map(|t| CommitType::Other(t.clone()))
+            .filter(|t| {
+                !CommitType::CONVENTIONAL
+                    .iter()
+                    .any(|c| c.as_str() == t.as_str())
+            }),
+    );
+    types
+}
+
+/// Builds the commit message from its parts, empty parts are left out
+fn format_message(
+    commit_type: &str,
+    scope: &str,
+    description: &str,
+    body: &str,
+    breaking: Option<&str>,
+    refs: &str,
+) -> String {
+    let mut message = String::from(commit_type.trim());
+
+    if !scope.trim().is_empty() {
+        message.push_str(&format!("({})", scope.trim()));
+    }
+    if breaking.is_some() {
+        message.push('!');
+    }
+    message.push_str(&format!(": {}", description.trim()));
+
+    if !body.trim().is_empty() {
+        message.push_str(&format!("\n\n{}", body.trim()));
+    }
+
+    let mut footers = Vec::<String>::new();
+
+    if let Some(note) = breaking.filter(|n| !n.trim().is_empty()) {
+        footers.push(format!("BREAKING CHANGE: {}", note.trim()));
+    }
+    if !refs.trim().is_empty() {
+        footers.push(format!("Refs: {}", refs.trim()));
+    }
+    if !footers.is_empty() {
+        message.push_str(&format!("\n\n{}", footers.join("\n")));
+    }
+
+    message
+}
+
+/// Collects the scopes used in the recent history of the repository, the most used first
+fn recent_scopes(repository: &Repository) -> Vec<String> {
+    let log = match repository.log("HEAD", "", "%s", Some(SCOPE_HISTORY)) {
+        Ok(l) => l,
+        Err(_) => {
+            trace!("Repository has no history to suggest scopes from");
+            return Vec::new();
+        }
+    };
+
+    let mut count = HashMap::<String, usize>::new();
+
+    for subject in log.lines() {
+        if let Some(message) = parser::parse(subject).message {
+            for scope in message.header.scopes.iter().filter(|s| !s.text.is_empty()) {
+                *count.entry(scope.text.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    let mut scopes: Vec<(String, usize)> = count.into_iter().collect();
+    scopes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    scopes.into_iter().map(|(s, _)| s).collect()
+}
+
+fn select_scope(theme: &ColorfulTheme, scopes: Vec<String>) -> String {
+    if scopes.is_empty() {
+        return optional_input(theme, "Scope (optional)");
+    }
+
+    let mut items = vec![String::from("<no scope>")];
+    items.extend(scopes);
+    items.push(String::from("<other scope>"));
+
+    let selected = prompt(
+        Select::with_theme(theme)
+            .with_prompt("Scope of the change")
+            .items(&items)
+            .default(0)
+            .interact(),
+    );
+
+    match selected {
+        0 => String::new(),
+        s if s == items.len() - 1 => optional_input(theme, "Scope"),
+        s => items.swap_remove(s),
+    }
+}
+
+fn optional_input(theme: &ColorfulTheme, message: &str) -> String {
+    prompt(
+        Input::<String>::with_theme(theme)
+            .with_prompt(message)
+            .allow_empty(true)
+            .interact_text(),
+    )
+}
+
+/// Unwraps the answer of a prompt, exits if the terminal is not interactive
+fn prompt<T>(answer: io::Result<T>) -> T {
+    match answer {
+        Ok(a) => a,
+        Err(e) => {
+            error!(
+                "Prompt failed, use the options to commit without a terminal; {}",
+                e
+            );
+            std::process::exit(exitcode::IOERR);
+        }
+    }
+}
+
+#[cfg(test)]
+mod commit_test {
+
+    use coco::CommitType;
+
+    use super::{format_message, type_choices};
+
+    #[test]
+    fn format_header() {
+        assert_eq!(
+            format_message("feat", "", "add parser", "", None, ""),
+            "feat: add parser"
+        );
+        assert_eq!(
+            format_message(" fix ", " parser, cli ", " remove typo ", " ", None, " "),
+            "fix(parser, cli): remove typo"
+        );
+    }
+
+    #[test]
+    fn format_breaking() {
+        assert_eq!(
+            format_message("feat", "api", "drop v1", "", Some(""), ""),
+            "feat(api)!: drop v1"
+        );
+        assert_eq!(
+            format_message("feat", "", "drop v1", "", Some("v1 is gone"), ""),
+            "feat!: drop v1\n\nBREAKING CHANGE: v1 is gone"
+        );
+    }
+
+    #[test]
+    fn format_body_and_footers() {
+        assert_eq!(
+            format_message(
+                "fix",
+                "",
+                "remove typo",
+                "The typo was in the readme\n",
+                Some("the readme moved"),
+                "#12, #14"
+            ),
+            "fix!: remove typo\n\nThe typo was in the readme\n\n\
+             BREAKING CHANGE: the readme moved\nRefs: #12, #14"
+        );
+        assert_eq!(
+            format_message("fix", "", "remove typo", "", None, "#12"),
+            "fix: remove typo\n\nRefs: #12"
+        );
+    }
+
+    #[test]
+    fn formatted_message_parses() {
+        let message = format_message(
+            "feat",
+            "cli",
+            "add commit command",
+            "Prompts for every part",
+            Some("removed the old command"),
+            "#3",
+        );
+        let commit = coco::Commit::parse(&message).unwrap();
+
+        assert_eq!(commit.commit_type, CommitType::Feature);
+        assert!(commit.breaking);
+        assert_eq!(commit.to_string(), message);
+    }
+
+    #[test]
+    fn custom_types() {
+        let types = type_choices(&[String::from("deps"), String::from("feat")]);
+
+        assert_eq!(
+            &types[..CommitType::CONVENTIONAL.len()],
+            &CommitType::CONVENTIONAL
+        );
+        assert_eq!(
+            &types[CommitType::CONVENTIONAL.len()..],
+            &[CommitType::Other(String::from("deps"))]
+        );
+    }
+}
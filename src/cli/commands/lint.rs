@@ -1,22 +1,39 @@
+use std::collections::HashSet;
+use std::fs;
 use std::path::PathBuf;
 
 use clap::{ArgGroup, Args};
 use coco::{
-    lint::{Level, LintResult},
+    lint::{Level, LintResult, Violation},
     Commit,
 };
 use coco_git::core::Repository;
+use coco_git::log::CommitRecord;
 use log::{error, info, trace, warn};
+use serde::Serialize;
+
+use super::baseline;
+use super::summary::{Outcome, Summary};
+use super::{git_exit_code, json, Format, INVALID_INPUT, SHORT_HASH, VIOLATIONS_FOUND};
+use crate::config::Config;
 
 #[derive(Args)]
 #[clap(group(
-    ArgGroup::new("source").required(true).args(&["message", "path"])
+    ArgGroup::new("source").required(true).args(&["message", "path", "edit"])
 ))]
 /// Lints a conventional commit message or the message of the last git commit
+///
+/// Exits with 65 if violations were found, 66 if the input is invalid (e.g. the path is not
+/// a repository) and 69 if git failed.
 pub struct Lint {
-    /// Defines the level at which the linter will return an error
-    #[clap(arg_enum, value_parser)]
-    pub level: Option<Level>,
+    /// Messages with a violation at this level or a more severe level fail the lint
+    #[clap(long = "fail-on", arg_enum, value_parser, default_value = "error")]
+    pub fail_on: Level,
+
+    /// Fails the lint if more warnings than this are found across all messages, warnings of
+    /// commits in the baseline are not counted
+    #[clap(long = "max-warnings", value_parser)]
+    pub max_warnings: Option<usize>,
 
     /// The specific conventional commit message you want to lint
     #[clap(short, long = "message")]
@@ -26,6 +43,17 @@ pub struct Lint {
     #[clap(short, long, value_parser)]
     pub path: Option<PathBuf>,
 
+    /// The path to a file containing the message, e.g. `.git/COMMIT_EDITMSG` in a commit-msg hook
+    ///
+    /// Comment lines starting with `#` are ignored, as git removes them from the message.
+    #[clap(short, long, value_parser)]
+    pub edit: Option<PathBuf>,
+
+    /// Applies the suggested fixes and prints the corrected message, or rewrites the file
+    /// in place if the message was read from a file with `--edit`
+    #[clap(short, long, conflicts_with = "path")]
+    pub fix: bool,
+
     /// Flag on how many commit messages of the repository shall be linted
     #[clap(short, value_parser, requires = "path")]
     pub count: Option<usize>,
@@ -38,12 +66,46 @@ pub struct Lint {
     // Flag that, if set to true, will filter commit messages that are without errors
     #[clap(short, long = "only-error")]
     pub only_error: bool,
+
+    /// Only prints the summary with the statistics of all linted commit messages
+    #[clap(short, long)]
+    pub quiet: bool,
+
+    /// The format of the report, `json` prints the violations and ignored rules of every
+    /// message and the summary as a single line for scripts
+    #[clap(
+        long,
+        arg_enum,
+        value_parser,
+        default_value = "plain",
+        conflicts_with = "fix"
+    )]
+    pub format: Format,
+
+    /// Only fails on commits that are not listed in the baseline file
+    #[clap(long, value_parser, requires = "path")]
+    pub baseline: Option<PathBuf>,
+
+    /// Writes the hashes of all failing commits to the baseline file, instead of failing
+    #[clap(long = "write-baseline", value_parser, requires = "path")]
+    pub write_baseline: Option<PathBuf>,
 }
 
 impl Lint {
-    pub fn lint(&self) {
+    pub fn lint(&self, config: &Config) {
         trace!("Starting linting functionality");
 
+        let root = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+        let rules = match config
+            .rules(&Repository::repo_root(&root).map_or(root, |r| PathBuf::from(r.trim_end())))
+        {
+            Ok(r) => r,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(INVALID_INPUT);
+            }
+        };
+
         let mut commit_to_lint = String::new();
 
         if self.message.is_some() {
@@ -53,73 +115,248 @@ impl Lint {
         }
         trace!("No specific conventional commit message provided");
 
+        if let Some(path) = &self.edit {
+            match fs::read_to_string(path) {
+                Ok(m) => {
+                    trace!("Linting the message in {}", path.to_string_lossy());
+                    commit_to_lint = strip_comments(&m);
+                }
+                Err(e) => {
+                    error!(
+                        "Could not read the message file \"{}\": {}",
+                        path.to_string_lossy(),
+                        e
+                    );
+                    std::process::exit(INVALID_INPUT);
+                }
+            }
+        }
+
+        let mut records = Vec::<CommitRecord>::new();
+
         if self.path.is_some() {
             let path = self.path.clone().unwrap();
             match Repository::new(path.as_path()) {
                 Ok(r) => {
                     trace!(
                         "Provided repository, working in root {}",
-                        Repository::repo_root(path.as_path()).unwrap_or(String::from("undefined"))
+                        r.path().display()
                     );
-                    let commit_res =
-                        r.log("HEAD", "", "%h»¦«%cn»¦«%ce»¦«%ct»¦«%s»¦«%»»»", self.count);
 
-                    if commit_res.is_err() {
-                        error!("Provided repository does not contain any commits!");
-                        std::process::exit(exitcode::DATAERR);
+                    match r.commits("HEAD", "", self.count, false) {
+                        Ok(c) => records = c,
+                        Err(e) => {
+                            error!("{}", e);
+                            std::process::exit(git_exit_code(&e));
+                        }
                     }
 
-                    commit_to_lint = commit_res.unwrap();
-
-                    trace!("Using commit messages for lint: {}", commit_to_lint);
+                    trace!("Using {} commit messages for lint", records.len());
                 }
-                Err(_) => {
-                    error!(
-                        "Given path \"{}\" is not a repository",
-                        path.to_string_lossy()
-                    );
-                    std::process::exit(exitcode::DATAERR);
+                Err(e) => {
+                    error!("{}", e);
+                    std::process::exit(git_exit_code(&e));
                 }
             }
         }
 
-        let mut success = true;
-        let mut count = usize::default();
+        // Commits from the log come with their details, a provided message does not
+        let messages: Vec<(Option<&str>, Option<&str>, &str)> = if self.path.is_some() {
+            records
+                .iter()
+                .map(|r| {
+                    (
+                        Some(r.hash.as_str()),
+                        Some(r.author_name.as_str()),
+                        r.message.as_str(),
+                    )
+                })
+                .collect()
+        } else {
+            vec![(None, None, commit_to_lint.as_str())]
+        };
+
+        let baseline = match &self.baseline {
+            Some(path) => match baseline::read(path) {
+                Ok(b) => b,
+                Err(e) => {
+                    error!(
+                        "Could not read the baseline \"{}\": {}",
+                        path.to_string_lossy(),
+                        e
+                    );
+                    std::process::exit(INVALID_INPUT);
+                }
+            },
+            None => HashSet::new(),
+        };
 
-        for commit in commit_to_lint.as_str().split("»»»") {
-            trace!("Linting message {}", commit);
-            count += 1;
+        let mut summary = Summary::default();
+        let mut failing = Vec::<String>::new();
+        let mut report = Vec::<Linted>::new();
 
-            if commit.trim().is_empty() {
+        for (hash, author, message) in messages {
+            trace!("Linting message {}", message);
+
+            if message.trim().is_empty() {
                 continue;
             };
 
-            let mut details = commit.split("»¦«");
+            let fixed;
+            let message = if self.fix {
+                fixed = Commit::fix_with(message, &rules);
+
+                if fixed != message {
+                    self.write_fix(&fixed);
+                }
+                fixed.as_str()
+            } else {
+                message
+            };
+
+            let lint_result = Commit::lint_with(message, &rules);
+            let outcome = if !lint_result.lints.iter().any(|l| l.level <= self.fail_on) {
+                Outcome::Passed
+            } else if hash.is_some_and(|h| baseline.contains(h)) {
+                Outcome::Baselined
+            } else {
+                Outcome::Failed
+            };
 
-            let hash = details.nth(0);
-            let message = details.nth(3);
+            if outcome != Outcome::Passed {
+                failing.extend(hash.map(String::from));
+            }
 
-            let lint_result = Commit::lint(&commit);
-            success &= print_lint_result(lint_result, hash, message, self.only_error);
+            summary.add(&lint_result, author, outcome);
+            if self.format == Format::Json {
+                report.push(Linted {
+                    hash: hash.map(String::from),
+                    subject: message.lines().next().unwrap_or_default().to_string(),
+                    outcome,
+                    violations: lint_result.lints,
+                    ignored: lint_result.ignored,
+                });
+            } else if !self.quiet {
+                print_lint_result(
+                    lint_result,
+                    hash.map(|h| &h[..h.len().min(SHORT_HASH)]),
+                    message,
+                    self.only_error,
+                );
+            }
         }
 
-        if !success {
-            if self.ignore_errors && count > 1 {
+        if self.format == Format::Json {
+            println!(
+                "{}",
+                json(&Report {
+                    commits: report,
+                    summary: &summary
+                })
+            );
+        } else if self.quiet || summary.checked > 1 {
+            summary.print();
+        }
+
+        if let Some(path) = &self.write_baseline {
+            if let Err(e) = baseline::write(path, &failing) {
+                error!(
+                    "Could not write the baseline \"{}\": {}",
+                    path.to_string_lossy(),
+                    e
+                );
+                std::process::exit(exitcode::IOERR);
+            }
+
+            if self.format == Format::Plain {
+                info!(
+                    "📝 Wrote {} failing commit(s) to the baseline {}",
+                    failing.len(),
+                    path.to_string_lossy()
+                );
+            }
+            return;
+        }
+
+        let warnings = summary.count(Level::Warning);
+        let too_many_warnings = self.max_warnings.is_some_and(|max| warnings > max);
+
+        if too_many_warnings {
+            error!(
+                "Found {} warnings, the maximum is {}",
+                warnings,
+                self.max_warnings.unwrap_or_default()
+            );
+        }
+
+        if summary.failed > 0 || too_many_warnings {
+            if self.ignore_errors && summary.checked > 1 {
                 return;
             }
 
-            std::process::exit(exitcode::DATAERR);
+            std::process::exit(VIOLATIONS_FOUND);
         }
     }
 }
 
-// returns false if the lint encountered a critical error
-fn print_lint_result(
+impl Lint {
+    /// Writes the fixed message back to the file given by `--edit` or prints it
+    fn write_fix(&self, fixed: &str) {
+        match &self.edit {
+            Some(path) => {
+                if let Err(e) = fs::write(path, fixed) {
+                    error!(
+                        "Could not write the fixed message to \"{}\": {}",
+                        path.to_string_lossy(),
+                        e
+                    );
+                    std::process::exit(exitcode::IOERR);
+                }
+                info!("🔧 Fixed the commit message in {}", path.to_string_lossy());
+            }
+            None => {
+                info!("🔧 Fixed commit message:");
+                println!("{}\n", fixed);
+            }
+        }
+    }
+}
+
+/// The report of all linted messages printed with `--format json`
+#[derive(Serialize)]
+struct Report<'a> {
+    commits: Vec<Linted>,
+    summary: &'a Summary,
+}
+
+/// The result of a linted message in the `json` report
+#[derive(Serialize)]
+struct Linted {
+    /// The hash of the commit, `None` if the message was not read from the history
+    hash: Option<String>,
+    subject: String,
+    outcome: Outcome,
+    violations: Vec<Violation>,
+    /// Violations of the rules that are ignored by a `Cocors-Ignore` footer in the message
+    ignored: Vec<Violation>,
+}
+
+/// Removes the comment lines git adds to the message file and everything below the
+/// scissors line of `git commit --verbose`
+fn strip_comments(message: &str) -> String {
+    message
+        .split_inclusive('\n')
+        .take_while(|l| !l.starts_with("# ------------------------ >8 ------------------------"))
+        .filter(|l| !l.starts_with('#'))
+        .collect()
+}
+
+pub(crate) fn print_lint_result(
     lint_result: LintResult,
     hash: Option<&str>,
-    message: Option<&str>,
+    message: &str,
     only_error: bool,
-) -> bool {
+) {
     if lint_result.lints.is_empty() && lint_result.commit.is_some() {
         if !only_error {
             println!(
@@ -128,7 +365,7 @@ fn print_lint_result(
             info!(
                 "✔️ : Your commit \"{}\" \"{}\" \n\t\t   is flawless, go ahead an push! ",
                 hash.map_or_else(String::new, |h| h.trim().to_string()),
-                message.map_or_else(String::new, |m| format!("{}", m.trim()))
+                message.lines().next().unwrap_or_default().trim()
             );
         }
     } else {
@@ -136,26 +373,31 @@ fn print_lint_result(
         info!(
             "🤓  Some remarks on commit \"{}\" \"{}\" \n ",
             hash.map_or_else(String::new, |h| h.trim().to_string()),
-            message.map_or_else(String::new, |m| format!("{}", m.trim()))
+            message.lines().next().unwrap_or_default().trim()
         );
         for lint in lint_result.lints {
+            let snippet = lint
+                .snippet(message)
+                .map_or_else(String::new, |s| format!("\n{}", s));
+
             match lint.level {
-                Level::Error => error!("{}", lint),
+                Level::Error => error!("{}{}", lint, snippet),
                 Level::Warning => {
-                    warn!("{}", lint)
+                    warn!("{}{}", lint, snippet)
                 }
                 _ => {
-                    info!("{}", lint)
+                    info!("{}{}", lint, snippet)
                 }
             };
         }
     }
 
-    println!("");
-
-    if lint_result.commit.is_none() {
-        return false;
+    for lint in lint_result.ignored {
+        info!(
+            "🙈 Ignored by Cocors-Ignore: [{}] {}",
+            lint.rule, lint.message
+        );
     }
 
-    return true;
+    println!();
 }
@@ -1,9 +1,53 @@
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
 
+mod baseline;
+mod commit;
 mod lint;
+mod summary;
+mod version;
 
+pub use commit::Commit;
 pub use lint::Lint;
+pub use version::Version;
+
+/// Exit code if violations at or above the `--fail-on` level have been found
+pub(crate) const VIOLATIONS_FOUND: i32 = exitcode::DATAERR;
+/// Exit code if the input is invalid, e.g. the path is not a repository or the configuration
+/// can not be read
+pub(crate) const INVALID_INPUT: i32 = exitcode::NOINPUT;
+/// Exit code if a git command failed
+pub(crate) const GIT_FAILURE: i32 = exitcode::UNAVAILABLE;
+
+/// Number of characters of a commit hash that are printed
+pub(crate) const SHORT_HASH: usize = 7;
+
+/// The output format of the lint and version commands
+#[derive(PartialEq, Eq, Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Plain,
+    Json,
+}
+
+/// Serializes the output of a command to a single line of JSON
+pub(crate) fn json(value: &impl Serialize) -> String {
+    serde_json::to_string(value).expect("The output is always serializable")
+}
+
+/// The exit code for a failed operation on a repository
+pub(crate) fn git_exit_code(e: &coco_git::Error) -> i32 {
+    match e {
+        coco_git::Error::NotARepository(_)
+        | coco_git::Error::UnknownRevision(_)
+        | coco_git::Error::EmptyRepository => INVALID_INPUT,
+        coco_git::Error::Io(_) => exitcode::IOERR,
+        _ => GIT_FAILURE,
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     Lint(Lint),
+    Commit(Commit),
+    Version(Version),
 }
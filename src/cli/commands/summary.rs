@@ -0,0 +1,228 @@
+use std::collections::{BTreeMap, HashMap};
+
+use coco::lint::{Level, LintResult};
+use log::info;
+use serde::Serialize;
+
+/// Number of rules and authors that are listed in the summary
+const TOP: usize = 5;
+
+/// The outcome of linting a single commit message
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Outcome {
+    Passed,
+    Failed,
+    /// The message failed, but the commit is listed in the baseline
+    Baselined,
+}
+
+/// Statistics over all linted commit messages of a run
+#[derive(Default, Serialize)]
+pub(crate) struct Summary {
+    /// Number of linted commit messages
+    pub checked: usize,
+    /// Number of commit messages that passed
+    pub passed: usize,
+    /// Number of commit messages that failed
+    pub failed: usize,
+    /// Number of commit messages that failed, but are listed in the baseline
+    pub baselined: usize,
+    /// Number of violations per level, violations of commits in the baseline are not counted
+    levels: BTreeMap<Level, usize>,
+    /// Number of violations per rule, violations of commits in the baseline are not counted
+    rules: HashMap<String, usize>,
+    /// Number of violations per rule that are ignored by a `Cocors-Ignore` footer
+    ignored: HashMap<String, usize>,
+    /// Number of failed commit messages per author
+    authors: HashMap<String, usize>,
+}
+
+impl Summary {
+    /// Adds the result of a linted commit message, `author` is `None` if the message was not
+    /// read from the history of a repository
+    pub fn add(&mut self, result: &LintResult, author: Option<&str>, outcome: Outcome) {
+        self.checked += 1;
+
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Baselined => self.baselined += 1,
+            Outcome::Failed => {
+                self.failed += 1;
+                if let Some(author) = author {
+                    *self.authors.entry(author.to_string()).or_default() += 1;
+                }
+            }
+        }
+
+        if outcome != Outcome::Baselined {
+            for lint in &result.lints {
+                *self.levels.entry(lint.level).or_default() += 1;
+                *self.rules.entry(lint.rule.clone()).or_default() += 1;
+            }
+        }
+
+        for lint in &result.ignored {
+            *self.ignored.entry(lint.rule.clone()).or_default() += 1;
+        }
+    }
+
+    /// Number of violations with the given level
+    pub fn count(&self, level: Level) -> usize {
+        self.levels.get(&level).copied().unwrap_or_default()
+    }
+
+    pub fn print(&self) {
+        println!("==========================================================================\n");
+        info!(
+            "📊 Summary: {} commit(s) checked, {} passed, {} failed{}",
+            self.checked,
+            self.passed,
+            self.failed,
+            if self.baselined > 0 {
+                format!(", {} failed but are in the baseline", self.baselined)
+            } else {
+                String::new()
+            }
+        );
+
+        if !self.levels.is_empty() {
+            info!(
+                "Violations: {}",
+                self.levels
+                    .iter()
+                    .map(|(level, count)| format!("{}{:?} {}", level, level, count))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+        }
+
+        if !self.rules.is_empty() {
+            info!("Most violated rules: {}", top(&self.rules));
+        }
+
+        if !self.ignored.is_empty() {
+            info!("Most ignored rules: {}", top(&self.ignored));
+        }
+
+        if !self.authors.is_empty() {
+            info!("Authors with most failed commits: {}", top(&self.authors));
+        }
+
+        println!();
+    }
+}
+
+/// Formats the entries with the highest counts, ties are ordered by name
+fn top(counts: &HashMap<String, usize>) -> String {
+    let mut entries: Vec<(&String, &usize)> = counts.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+    entries
+        .iter()
+        .take(TOP)
+        .map(|(name, count)| format!("{} ({})", name, count))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod summary_test {
+
+    use std::collections::HashMap;
+
+    use coco::lint::Level;
+    use coco::Commit;
+
+    use super::{top, Outcome, Summary};
+
+    #[test]
+    fn empty_range() {
+        let summary = Summary::default();
+
+        assert_eq!(summary.checked, 0);
+        assert_eq!(summary.passed + summary.failed + summary.baselined, 0);
+        assert_eq!(summary.count(Level::Error), 0);
+        assert!(summary.rules.is_empty());
+        assert!(summary.authors.is_empty());
+    }
+
+    #[test]
+    fn count_outcomes() {
+        let mut summary = Summary::default();
+        summary.add(
+            &Commit::lint("feat: add parser"),
+            Some("A"),
+            Outcome::Passed,
+        );
+        summary.add(&Commit::lint("add parser"), Some("B"), Outcome::Failed);
+        summary.add(&Commit::lint("update"), Some("B"), Outcome::Failed);
+        summary.add(&Commit::lint("change"), Some("C"), Outcome::Baselined);
+        summary.add(&Commit::lint("fixed"), None, Outcome::Failed);
+
+        assert_eq!(summary.checked, 5);
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.failed, 3);
+        assert_eq!(summary.baselined, 1);
+        assert_eq!(summary.authors, HashMap::from([(String::from("B"), 2)]));
+    }
+
+    #[test]
+    fn count_levels_and_rules() {
+        let unconventional = Commit::lint("add parser");
+        assert!(unconventional.commit.is_none());
+
+        let mut summary = Summary::default();
+        summary.add(&unconventional, None, Outcome::Failed);
+        summary.add(&Commit::lint("feat: add parser."), None, Outcome::Passed);
+        summary.add(&Commit::lint("fix: remove typo."), None, Outcome::Passed);
+
+        let errors = unconventional
+            .lints
+            .iter()
+            .filter(|l| l.level == Level::Error)
+            .count();
+        assert!(errors > 0);
+        assert_eq!(summary.count(Level::Error), errors);
+        assert_eq!(summary.count(Level::Warning), 2);
+        assert_eq!(summary.rules.get("subject-full-stop"), Some(&2));
+        for lint in &unconventional.lints {
+            assert!(summary.rules.contains_key(&lint.rule));
+        }
+    }
+
+    #[test]
+    fn skip_violations_of_baseline() {
+        let mut summary = Summary::default();
+        summary.add(&Commit::lint("feat: add parser."), None, Outcome::Baselined);
+        summary.add(&Commit::lint("fix: remove typo"), None, Outcome::Passed);
+
+        assert_eq!(summary.baselined, 1);
+        assert_eq!(summary.count(Level::Warning), 0);
+        assert_eq!(summary.rules.get("subject-full-stop"), None);
+    }
+
+    #[test]
+    fn count_ignored_rules() {
+        let result = Commit::lint("feat: add parser.\n\nCocors-Ignore: subject-full-stop");
+        assert!(result.lints.iter().all(|l| l.rule != "subject-full-stop"));
+
+        let mut summary = Summary::default();
+        summary.add(&result, None, Outcome::Passed);
+
+        assert_eq!(summary.count(Level::Warning), 0);
+        assert_eq!(summary.rules.get("subject-full-stop"), None);
+        assert_eq!(summary.ignored.get("subject-full-stop"), Some(&1));
+    }
+
+    #[test]
+    fn top_entries() {
+        let counts = HashMap::from([
+            (String::from("b"), 2),
+            (String::from("a"), 2),
+            (String::from("c"), 5),
+        ]);
+
+        assert_eq!(top(&counts), "c (5), a (2), b (2)");
+    }
+}
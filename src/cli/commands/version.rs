@@ -0,0 +1,536 @@
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use coco::{Bump, Channel, Commit, ParseVersionError};
+use coco_git::core::Repository;
+use coco_git::log::CommitRecord;
+use log::{debug, error, info, trace};
+use serde::Serialize;
+
+use super::{git_exit_code, json, Format, INVALID_INPUT, SHORT_HASH, VIOLATIONS_FOUND};
+use crate::config::Config;
+
+/// The number of commits fetched at a time when deepening a shallow clone
+const DEEPEN_STEP: usize = 100;
+
+#[derive(Args)]
+/// Queries the released versions of the repository and computes the next version
+///
+/// Releases are the tags that are a semantic version, optionally prefixed with `v`. Without a
+/// subcommand the next version is computed like `cocors version next`.
+pub struct Version {
+    /// The path to the repository, defaults to the current directory
+    #[clap(short, long, value_parser, global = true)]
+    pub path: Option<PathBuf>,
+
+    /// The format of the output, `json` prints a single line for scripts
+    #[clap(long, arg_enum, value_parser, default_value = "plain", global = true)]
+    pub format: Format,
+
+    /// Fetches more history of a shallow clone until the latest release is reachable from HEAD,
+    /// instead of failing because the release is missing
+    #[clap(long, global = true)]
+    pub deepen: bool,
+
+    /// The arguments of `next`, if no subcommand is given
+    #[clap(flatten)]
+    pub next: Next,
+
+    #[clap(subcommand)]
+    pub command: Option<VersionCommand>,
+}
+
+#[derive(Subcommand)]
+pub enum VersionCommand {
+    Current(Current),
+    Next(Next),
+    List(List),
+    Compare(Compare),
+}
+
+#[derive(Args)]
+/// Prints the latest release contained in HEAD
+///
+/// Exits with 66 if no release is found.
+pub struct Current {
+    /// Ignores pre-releases
+    #[clap(long)]
+    pub stable: bool,
+}
+
+#[derive(Args)]
+/// Computes the next version from the conventional commits since the latest release and
+/// lists the commits that caused the bump
+///
+/// The version is released on the channel of the checked out branch, see `channels` in the
+/// configuration. Without channels every branch releases stable versions.
+///
+/// Exits with 65 if the commits require a bump the channel does not allow and 66 if the
+/// channels are misconfigured, the branch does not match a channel or the metadata template
+/// is invalid.
+pub struct Next {
+    /// Uses the release channel of this branch instead of the checked out branch
+    #[clap(short, long)]
+    pub branch: Option<String>,
+
+    /// Sets the build metadata of the version from a template (e.g. `{branch}.{short_sha}`)
+    ///
+    /// The placeholders are `{sha}` and `{short_sha}` of HEAD, `{commit_count}` since the
+    /// latest release, `{date}` of HEAD as `YYYYMMDD` and `{branch}`. Characters that are not
+    /// allowed in build metadata are replaced by `-`.
+    #[clap(short, long, value_name = "TEMPLATE")]
+    pub metadata: Option<String>,
+
+    /// Prints the development snapshot of the next version (e.g. `1.3.0-alpha.5+g1a2b3c`, or
+    /// `1.3.0-beta.1.5+g1a2b3c` on a `beta` channel), with the number of commits since the
+    /// latest release and the hash of HEAD
+    #[clap(long, conflicts_with = "metadata")]
+    pub dev: bool,
+
+    /// Only prints the version, without the commits that caused the bump
+    #[clap(short, long)]
+    pub quiet: bool,
+}
+
+impl Next {
+    /// Checks if any option is given
+    fn is_set(&self) -> bool {
+        self.branch.is_some() || self.metadata.is_some() || self.dev || self.quiet
+    }
+}
+
+#[derive(Args)]
+/// Lists all release tags, sorted from the lowest to the highest version
+pub struct List {
+    /// Ignores pre-releases
+    #[clap(long)]
+    pub stable: bool,
+}
+
+#[derive(Args)]
+/// Compares two versions by their precedence and prints `less`, `equal` or `greater`
+///
+/// The build metadata does not affect the precedence. Exits with 66 if a version is invalid.
+pub struct Compare {
+    /// The version that is compared, optionally prefixed with `v`
+    pub a: String,
+
+    /// The version it is compared to, optionally prefixed with `v`
+    pub b: String,
+}
+
+/// A release tag and its version
+#[derive(Serialize)]
+struct Release {
+    tag: String,
+    version: String,
+}
+
+/// A commit that caused the bump of the next version
+#[derive(Serialize)]
+struct Cause {
+    hash: String,
+    bump: String,
+    subject: String,
+}
+
+/// The next version and the commits that caused it
+#[derive(Serialize)]
+struct NextVersion {
+    version: String,
+    latest: Option<Release>,
+    channel: String,
+    bump: String,
+    commits: Vec<Cause>,
+}
+
+#[derive(Serialize)]
+struct Comparison {
+    a: String,
+    b: String,
+    ordering: String,
+}
+
+impl Version {
+    pub fn version(&self, config: &Config) {
+        trace!("Starting version functionality");
+
+        if self.command.is_some() && self.next.is_set() {
+            error!("The options of `cocors version next` must be given after the subcommand");
+            std::process::exit(exitcode::USAGE);
+        }
+
+        match &self.command {
+            None => self.next(&self.next, config),
+            Some(VersionCommand::Current(args)) => self.current(args),
+            Some(VersionCommand::Next(args)) => self.next(args, config),
+            Some(VersionCommand::List(args)) => self.list(args),
+            Some(VersionCommand::Compare(args)) => self.compare(args),
+        }
+    }
+
+    fn current(&self, args: &Current) {
+        let repository = self.repository();
+
+        let (tag, version) = match latest(&repository, args.stable, self.deepen) {
+            Some(l) => l,
+            None => {
+                error!("No release found in {}", repository.path().display());
+                std::process::exit(INVALID_INPUT);
+            }
+        };
+
+        match self.format {
+            Format::Plain => println!("{}", version),
+            Format::Json => println!(
+                "{}",
+                json(&Release {
+                    tag,
+                    version: version.to_string()
+                })
+            ),
+        }
+    }
+
+    fn next(&self, args: &Next, config: &Config) {
+        let channels = match config.channels() {
+            Ok(c) => c,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(INVALID_INPUT);
+            }
+        };
+
+        let repository = self.repository();
+
+        let branch = match &args.branch {
+            Some(b) => Some(b.clone()),
+            None => repository.current_branch().unwrap_or_else(|e| exit_with(e)),
+        };
+
+        let channel = match &branch {
+            _ if channels.is_empty() => Channel::new("*"),
+            Some(branch) => match Channel::find(&channels, branch) {
+                Some(c) => c.clone(),
+                None => {
+                    error!("The branch \"{}\" does not match a release channel", branch);
+                    std::process::exit(INVALID_INPUT);
+                }
+            },
+            None => {
+                error!("HEAD is detached, choose the release channel with --branch");
+                std::process::exit(INVALID_INPUT);
+            }
+        };
+        debug!(
+            "Releasing on the channel of the branches {}",
+            channel.branch
+        );
+
+        let releases: Vec<coco::Version> =
+            releases(repository.tags().unwrap_or_else(|e| exit_with(e)), false)
+                .into_iter()
+                .map(|(_, v)| v)
+                .collect();
+
+        // The latest stable release contained in HEAD is the base of the next version
+        let latest = latest(&repository, true, self.deepen);
+
+        let history = match &latest {
+            Some((tag, version)) => {
+                debug!("Latest release is {} (tag {})", version, tag);
+                repository.commits(tag, "HEAD", None, false)
+            }
+            None => {
+                debug!("No release found, starting at 0.0.0");
+                repository.commits("HEAD", "", None, false)
+            }
+        }
+        .unwrap_or_else(|e| exit_with(e));
+
+        let commits: Vec<(&CommitRecord, Commit)> = history
+            .iter()
+            .filter_map(|r| {
+                let commit = Commit::parse(&r.message);
+                if commit.is_none() {
+                    debug!("Ignoring the unconventional commit {}", r.hash);
+                }
+                commit.map(|c| (r, c))
+            })
+            .collect();
+        let conventional: Vec<Commit> = commits.iter().map(|(_, c)| c.clone()).collect();
+
+        let base = latest.as_ref().map(|(_, v)| v.clone()).unwrap_or_default();
+
+        // HEAD is the release itself if there are no commits since
+        let head = || match history.first() {
+            Some(h) => h.clone(),
+            None => repository
+                .commits("HEAD", "", Some(1), false)
+                .unwrap_or_else(|e| exit_with(e))
+                .swap_remove(0),
+        };
+
+        let next = if args.dev {
+            let hash = head().hash;
+            channel.dev_version(
+                &base,
+                &releases,
+                &conventional,
+                history.len(),
+                &hash[..hash.len().min(SHORT_HASH)],
+            )
+        } else {
+            channel.next_version(&base, &releases, &conventional)
+        };
+        let mut version = match next {
+            Ok(v) => v,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(VIOLATIONS_FOUND);
+            }
+        };
+
+        if let Some(template) = &args.metadata {
+            match metadata(template, &head(), branch.as_deref(), history.len()) {
+                Ok(m) => version.metadata = Some(m),
+                Err(e) => {
+                    error!("{}", e);
+                    std::process::exit(INVALID_INPUT);
+                }
+            }
+        }
+
+        // The commits with the highest bump caused the version, as any of them alone would
+        let bump = conventional
+            .iter()
+            .map(Commit::bump_level)
+            .max()
+            .unwrap_or_default();
+        let causes: Vec<Cause> = commits
+            .iter()
+            .filter(|(_, c)| bump != Bump::None && c.bump_level() == bump)
+            .map(|(r, _)| Cause {
+                hash: r.hash.clone(),
+                bump: bump.to_string(),
+                subject: r.message.lines().next().unwrap_or_default().to_string(),
+            })
+            .collect();
+
+        match self.format {
+            Format::Plain => {
+                println!("{}", version);
+
+                if args.quiet {
+                    return;
+                }
+
+                let from = base.to_string();
+                if causes.is_empty() {
+                    info!("No commit since {} bumps the version", from);
+                } else {
+                    info!("{} bump from {} caused by:", bump, from);
+                }
+                for cause in &causes {
+                    info!(
+                        "  {} {}",
+                        &cause.hash[..SHORT_HASH.min(cause.hash.len())],
+                        cause.subject
+                    );
+                }
+            }
+            Format::Json => println!(
+                "{}",
+                json(&NextVersion {
+                    version: version.to_string(),
+                    latest: latest.map(|(tag, v)| Release {
+                        tag,
+                        version: v.to_string()
+                    }),
+                    channel: channel.branch,
+                    bump: bump.to_string(),
+                    commits: causes,
+                })
+            ),
+        }
+    }
+
+    fn list(&self, args: &List) {
+        let repository = self.repository();
+        let releases = releases(
+            repository.tags().unwrap_or_else(|e| exit_with(e)),
+            args.stable,
+        );
+
+        match self.format {
+            Format::Plain => {
+                for (tag, _) in releases {
+                    println!("{}", tag);
+                }
+            }
+            Format::Json => {
+                let releases: Vec<Release> = releases
+                    .into_iter()
+                    .map(|(tag, v)| Release {
+                        tag,
+                        version: v.to_string(),
+                    })
+                    .collect();
+                println!("{}", json(&releases));
+            }
+        }
+    }
+
+    fn compare(&self, args: &Compare) {
+        let parse = |version: &str| match release(version) {
+            Ok(v) => v,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(INVALID_INPUT);
+            }
+        };
+
+        let ordering = match parse(&args.a).cmp(&parse(&args.b)) {
+            Ordering::Less => "less",
+            Ordering::Equal => "equal",
+            Ordering::Greater => "greater",
+        };
+
+        match self.format {
+            Format::Plain => println!("{}", ordering),
+            Format::Json => println!(
+                "{}",
+                json(&Comparison {
+                    a: args.a.clone(),
+                    b: args.b.clone(),
+                    ordering: ordering.to_string(),
+                })
+            ),
+        }
+    }
+
+    fn repository(&self) -> Repository {
+        let path = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+
+        Repository::new(path.as_path()).unwrap_or_else(|e| exit_with(e))
+    }
+}
+
+/// The release tags with their versions, sorted by version and then by tag
+fn releases(tags: Vec<String>, stable: bool) -> Vec<(String, coco::Version)> {
+    let mut releases: Vec<(String, coco::Version)> = tags
+        .into_iter()
+        .filter_map(|t| release(&t).ok().map(|v| (t, v)))
+        .filter(|(_, v)| !stable || v.pre_release.is_none())
+        .collect();
+
+    releases.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    releases
+}
+
+/// The latest release contained in HEAD
+///
+/// Exits if no release is found in a shallow clone, as the release may not have been fetched.
+/// With `deepen`, the clone is deepened until a release is found or the history is complete.
+fn latest(repository: &Repository, stable: bool, deepen: bool) -> Option<(String, coco::Version)> {
+    let tags = if deepen {
+        repository.deepen_until_tag(DEEPEN_STEP, |t| {
+            release(t).is_ok_and(|v| !stable || v.pre_release.is_none())
+        })
+    } else {
+        repository.reachable_tags("HEAD")
+    }
+    .unwrap_or_else(|e| exit_with(e));
+    let latest = releases(tags, stable).pop();
+
+    if latest.is_none() && repository.is_shallow().unwrap_or_else(|e| exit_with(e)) {
+        exit_with(coco_git::Error::ShallowRepository);
+    }
+
+    latest
+}
+
+/// Resolves the placeholders of the build metadata template, with the details of HEAD and the
+/// number of commits since the latest release
+fn metadata(
+    template: &str,
+    head: &CommitRecord,
+    branch: Option<&str>,
+    commit_count: usize,
+) -> Result<String, String> {
+    let mut metadata = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed placeholder in the metadata \"{}\"", template))?
+            + start;
+
+        let value = match &rest[start + 1..end] {
+            "sha" => head.hash.clone(),
+            "short_sha" => head.hash[..head.hash.len().min(SHORT_HASH)].to_string(),
+            "commit_count" => commit_count.to_string(),
+            "date" => date(head.timestamp),
+            "branch" => branch
+                .ok_or("HEAD is detached, set the branch of the metadata with --branch")?
+                .to_string(),
+            p => return Err(format!("Unknown placeholder \"{{{}}}\" in the metadata", p)),
+        };
+
+        metadata.push_str(&rest[..start]);
+        // Build metadata only allows ASCII alphanumerics and hyphens in its identifiers
+        metadata.extend(value.chars().map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' => c,
+            _ => '-',
+        }));
+        rest = &rest[end + 1..];
+    }
+    metadata.push_str(rest);
+
+    let valid = metadata
+        .split('.')
+        .all(|i| !i.is_empty() && i.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+
+    match valid {
+        true => Ok(metadata),
+        false => Err(format!(
+            "Invalid build metadata \"{}\", only dot separated identifiers of ASCII alphanumerics and hyphens are allowed",
+            metadata
+        )),
+    }
+}
+
+/// Formats the date of a Unix timestamp in UTC as `YYYYMMDD`
+fn date(timestamp: i64) -> String {
+    // Converts the days since the epoch to the civil date, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = timestamp.div_euclid(86_400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+/// The version of a release tag, which is a semantic version optionally prefixed with `v`
+fn release(tag: &str) -> Result<coco::Version, ParseVersionError> {
+    tag.strip_prefix('v').unwrap_or(tag).parse()
+}
+
+/// Logs the failed operation on the repository and exits
+fn exit_with(e: coco_git::Error) -> ! {
+    error!("{}", e);
+    std::process::exit(git_exit_code(&e));
+}
@@ -0,0 +1,548 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+use coco::lint::{Case, Level, Pattern, Rule, Rules};
+use coco::{Bump, Channel};
+use serde::Deserialize;
+use serde_yaml::Value;
+
+/// Name of the configuration file that is looked up in the current directory
+pub const CONFIG_FILE: &str = ".cocors.yaml";
+
+/// Configuration of cocors, read from a yaml file
+///
+/// ```yaml
+/// types: [deps, release]
+/// lint:
+///   header-max-length:
+///     level: error
+///     value: 100
+///   subject-case: { level: warning, value: lower }
+///   subject-imperative: false
+///   body-required:
+///     level: error
+///     value: [feat, fix]
+///   footer-references: { level: warning, value: "^(Refs|Closes):? #\\d+" }
+///   scope-enum: { level: error, value: workspace }
+/// channels:
+///   - branch: main
+///   - branch: next
+///     pre-release: beta
+///   - branch: release/*
+///     bump: patch
+/// ```
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// Custom commit types offered by `cocors commit` in addition to the conventional types
+    pub types: Vec<String>,
+    /// Rules of the linter by their name, rules that are not configured keep their default
+    pub lint: HashMap<String, RuleConfig>,
+    /// Release channels of `cocors version`, the first channel matching the branch is used
+    pub channels: Vec<ChannelConfig>,
+}
+
+/// Configuration of a release channel
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ChannelConfig {
+    /// The pattern of the branch names, `*` matches any characters
+    pub branch: String,
+    /// The pre-release identifier of the versions, stable versions are released without one
+    #[serde(default)]
+    pub pre_release: Option<String>,
+    /// The highest allowed bump (`major`, `minor` or `patch`), defaults to `major`
+    #[serde(default)]
+    pub bump: Option<String>,
+}
+
+/// Configuration of a single lint rule
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum RuleConfig {
+    /// `false` disables the rule, `true` enables it with its default level and value
+    Toggle(bool),
+    /// Enables the rule at the given level with its default value
+    Level(String),
+    /// Enables the rule at the given level with the given value
+    Rule { level: String, value: Value },
+}
+
+impl Config {
+    /// Loads the configuration from the given file or, if no file is given, from the
+    /// [CONFIG_FILE] in the current directory if it exists
+    pub fn load(path: Option<&Path>) -> Result<Config, String> {
+        let path = match path {
+            Some(p) => p,
+            None if Path::new(CONFIG_FILE).is_file() => Path::new(CONFIG_FILE),
+            None => return Ok(Config::default()),
+        };
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Configuration \"{}\" could not be read; {}",
+                path.display(),
+                e
+            )
+        })?;
+
+        serde_yaml::from_str(&content)
+            .map_err(|e| format!("Configuration \"{}\" is not valid; {}", path.display(), e))
+    }
+
+    /// Builds the lint rules from the default [Rules] and the configured overrides
+    ///
+    /// `root` is the directory of the repository, used to derive the allowed scopes
+    pub fn rules(&self, root: &Path) -> Result<Rules, String> {
+        let mut rules = Rules::default();
+
+        for (name, config) in &self.lint {
+            match name.as_str() {
+                "type-case" => {
+                    rules.type_case = value_rule(name, rules.type_case.take(), config, case)?
+                }
+                "header-max-length" => {
+                    rules.header_max_length =
+                        value_rule(name, rules.header_max_length.take(), config, number)?
+                }
+                "header-trim" => rules.header_trim = level_rule(name, rules.header_trim, config)?,
+                "subject-case" => {
+                    rules.subject_case = value_rule(name, rules.subject_case.take(), config, case)?
+                }
+                "subject-full-stop" => {
+                    rules.subject_full_stop = level_rule(name, rules.subject_full_stop, config)?
+                }
+                "subject-imperative" => {
+                    rules.subject_imperative = level_rule(name, rules.subject_imperative, config)?
+                }
+                "body-leading-blank" => {
+                    rules.body_leading_blank = level_rule(name, rules.body_leading_blank, config)?
+                }
+                "footer-leading-blank" => {
+                    rules.footer_leading_blank =
+                        level_rule(name, rules.footer_leading_blank, config)?
+                }
+                "body-max-line-length" => {
+                    rules.body_max_line_length =
+                        value_rule(name, rules.body_max_line_length.take(), config, number)?
+                }
+                "body-required" => {
+                    rules.body_required =
+                        value_rule(name, rules.body_required.take(), config, string_list)?
+                }
+                "body-required-breaking" => {
+                    rules.body_required_breaking =
+                        level_rule(name, rules.body_required_breaking, config)?
+                }
+                "footer-token-format" => {
+                    rules.footer_token_format = level_rule(name, rules.footer_token_format, config)?
+                }
+                "footer-references" => {
+                    rules.footer_references =
+                        value_rule(name, rules.footer_references.take(), config, pattern)?
+                }
+                "scope-enum" => {
+                    rules.scope_enum = value_rule(name, rules.scope_enum.take(), config, |v| {
+                        scope_list(v, root)
+                    })?
+                }
+                "scope-case" => {
+                    rules.scope_case = value_rule(name, rules.scope_case.take(), config, case)?
+                }
+                _ => return Err(format!("Unknown lint rule \"{}\" in configuration", name)),
+            }
+        }
+
+        Ok(rules)
+    }
+
+    /// Builds the configured release channels in the order they are matched
+    pub fn channels(&self) -> Result<Vec<Channel>, String> {
+        self.channels
+            .iter()
+            .map(|c| {
+                let invalid = |what: &str, value: &str| {
+                    format!(
+                        "Invalid {} \"{}\" for release channel \"{}\"",
+                        what, value, c.branch
+                    )
+                };
+
+                if let Some(pre_release) = &c.pre_release {
+                    if pre_release.is_empty()
+                        || !pre_release
+                            .chars()
+                            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+                    {
+                        return Err(invalid("pre-release identifier", pre_release));
+                    }
+                }
+
+                let max_bump = match c.bump.as_deref().map(str::to_lowercase).as_deref() {
+                    None | Some("major") => Bump::Major,
+                    Some("minor") => Bump::Minor,
+                    Some("patch") => Bump::Patch,
+                    Some(_) => return Err(invalid("bump", c.bump.as_deref().unwrap_or_default())),
+                };
+
+                Ok(Channel {
+                    branch: c.branch.clone(),
+                    pre_release: c.pre_release.clone(),
+                    max_bump,
+                })
+            })
+            .collect()
+    }
+}
+
+fn number(value: &Value) -> Result<usize, String> {
+    value
+        .as_u64()
+        .map(|n| n as usize)
+        .ok_or_else(|| String::from("expected a positive number"))
+}
+
+fn case(value: &Value) -> Result<Case, String> {
+    value
+        .as_str()
+        .and_then(|s| Case::from_str(s, true).ok())
+        .ok_or_else(|| String::from("expected one of lower, sentence, start or upper"))
+}
+
+fn string_list(value: &Value) -> Result<Vec<String>, String> {
+    value
+        .as_sequence()
+        .and_then(|s| s.iter().map(|t| t.as_str().map(String::from)).collect())
+        .ok_or_else(|| String::from("expected a list of strings"))
+}
+
+/// Compiles the regex once, so an invalid pattern is rejected before any commit is linted
+fn pattern(value: &Value) -> Result<Pattern, String> {
+    let pattern = value
+        .as_str()
+        .ok_or_else(|| String::from("expected a regex"))?;
+
+    Pattern::new(pattern).map_err(|e| format!("\"{}\" is not a valid regex; {}", pattern, e))
+}
+
+/// Resolves the allowed scopes, which are either given as a list or derived from the repository:
+/// `workspace` allows the names of the crates in the cargo workspace and `directories` the
+/// names of the top level directories
+fn scope_list(value: &Value, root: &Path) -> Result<Vec<String>, String> {
+    match value.as_str() {
+        Some("workspace") => workspace_crates(root),
+        Some("directories") => top_level_directories(root),
+        Some(other) => Err(format!(
+            "\"{}\" is not a list of scopes, expected a list, \"workspace\" or \"directories\"",
+            other
+        )),
+        None => string_list(value),
+    }
+}
+
+fn package_name(manifest: &Path) -> Option<String> {
+    let manifest: toml::Value = fs::read_to_string(manifest).ok()?.parse().ok()?;
+
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(String::from)
+}
+
+/// Collects the name of the root package and all members of the workspace, members can be
+/// given as directory or as `directory/*` to include all crates in that directory
+fn workspace_crates(root: &Path) -> Result<Vec<String>, String> {
+    let manifest_path = root.join("Cargo.toml");
+    let manifest: toml::Value = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("\"{}\" could not be read; {}", manifest_path.display(), e))?
+        .parse()
+        .map_err(|e| format!("\"{}\" is not valid; {}", manifest_path.display(), e))?;
+
+    let mut crates: Vec<String> = package_name(&manifest_path).into_iter().collect();
+
+    let members = manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    for member in members.iter().filter_map(|m| m.as_str()) {
+        let directories = match member.strip_suffix("/*") {
+            Some(parent) => fs::read_dir(root.join(parent))
+                .map_err(|e| format!("Workspace members \"{}\" could not be read; {}", member, e))?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .collect(),
+            None => vec![root.join(member)],
+        };
+
+        crates.extend(
+            directories
+                .iter()
+                .filter_map(|d| package_name(&d.join("Cargo.toml"))),
+        );
+    }
+
+    crates.sort_unstable();
+    crates.dedup();
+    Ok(crates)
+}
+
+/// Collects the names of all directories in the root, hidden directories and the `target`
+/// directory are ignored
+fn top_level_directories(root: &Path) -> Result<Vec<String>, String> {
+    let mut directories = fs::read_dir(root)
+        .map_err(|e| format!("\"{}\" could not be read; {}", root.display(), e))?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|n| !n.starts_with('.') && n != "target")
+        .collect::<Vec<String>>();
+
+    directories.sort_unstable();
+    Ok(directories)
+}
+
+fn parse_level(name: &str, level: &str) -> Result<Level, String> {
+    Level::from_str(level, true)
+        .map_err(|_| format!("Invalid level \"{}\" for lint rule \"{}\"", level, name))
+}
+
+/// Applies the configuration of a rule that is only configured by its level
+fn level_rule(
+    name: &str,
+    default: Option<Level>,
+    config: &RuleConfig,
+) -> Result<Option<Level>, String> {
+    match config {
+        RuleConfig::Toggle(false) => Ok(None),
+        RuleConfig::Toggle(true) => Ok(Some(default.unwrap_or(Level::Warning))),
+        RuleConfig::Level(level) => parse_level(name, level).map(Some),
+        RuleConfig::Rule { .. } => Err(format!("Lint rule \"{}\" does not take a value", name)),
+    }
+}
+
+/// Applies the configuration of a rule that is configured by its level and a value
+fn value_rule<T>(
+    name: &str,
+    default: Option<Rule<T>>,
+    config: &RuleConfig,
+    parse: impl Fn(&Value) -> Result<T, String>,
+) -> Result<Option<Rule<T>>, String> {
+    let missing_value = || format!("Lint rule \"{}\" requires a value", name);
+
+    match config {
+        RuleConfig::Toggle(false) => Ok(None),
+        RuleConfig::Toggle(true) => default.map(Some).ok_or_else(missing_value),
+        RuleConfig::Level(level) => {
+            let level = parse_level(name, level)?;
+            default
+                .map(|r| Some(Rule::new(level, r.value)))
+                .ok_or_else(missing_value)
+        }
+        RuleConfig::Rule { level, value } => {
+            let level = parse_level(name, level)?;
+            parse(value)
+                .map(|v| Some(Rule::new(level, v)))
+                .map_err(|e| format!("Invalid value for lint rule \"{}\"; {}", name, e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod config_test {
+
+    use std::path::Path;
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use coco::lint::{Case, Level, Pattern, Rule, Rules};
+
+    use super::{scope_list, Config};
+
+    fn rules(yaml: &str) -> Result<Rules, String> {
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        config.rules(Path::new("."))
+    }
+
+    #[test]
+    fn default_rules() {
+        assert_eq!(rules("{}").unwrap(), Rules::default());
+    }
+
+    #[test]
+    fn configure_rules() {
+        let rules = rules(
+            "lint:
+               header-max-length: { level: error, value: 100 }
+               subject-case: { level: warning, value: lower }
+               subject-imperative: false
+               header-trim: error
+               body-required-breaking: true
+               body-required: { level: info, value: [feat, fix] }",
+        )
+        .unwrap();
+
+        assert_eq!(rules.header_max_length, Some(Rule::new(Level::Error, 100)));
+        assert_eq!(
+            rules.subject_case,
+            Some(Rule::new(Level::Warning, Case::Lower))
+        );
+        assert_eq!(rules.subject_imperative, None);
+        assert_eq!(rules.header_trim, Some(Level::Error));
+        assert_eq!(rules.body_required_breaking, Some(Level::Warning));
+        assert_eq!(
+            rules.body_required,
+            Some(Rule::new(
+                Level::Info,
+                vec![String::from("feat"), String::from("fix")]
+            ))
+        );
+        assert_eq!(rules.type_case, Rules::default().type_case);
+    }
+
+    #[test]
+    fn level_keeps_default_value() {
+        let rules = rules("lint: { header-max-length: error }").unwrap();
+
+        assert_eq!(rules.header_max_length, Some(Rule::new(Level::Error, 72)));
+    }
+
+    #[test]
+    fn unknown_rule() {
+        assert_eq!(
+            rules("lint: { header-length: error }").unwrap_err(),
+            "Unknown lint rule \"header-length\" in configuration"
+        );
+    }
+
+    #[test]
+    fn invalid_level() {
+        assert_eq!(
+            rules("lint: { header-trim: fatal }").unwrap_err(),
+            "Invalid level \"fatal\" for lint rule \"header-trim\""
+        );
+        assert_eq!(
+            rules("lint: { header-max-length: { level: fatal, value: 50 } }").unwrap_err(),
+            "Invalid level \"fatal\" for lint rule \"header-max-length\""
+        );
+    }
+
+    #[test]
+    fn invalid_value() {
+        assert_eq!(
+            rules("lint: { header-max-length: { level: error, value: long } }").unwrap_err(),
+            "Invalid value for lint rule \"header-max-length\"; expected a positive number"
+        );
+        assert_eq!(
+            rules("lint: { header-trim: { level: error, value: 1 } }").unwrap_err(),
+            "Lint rule \"header-trim\" does not take a value"
+        );
+        assert_eq!(
+            rules("lint: { subject-case: true }").unwrap_err(),
+            "Lint rule \"subject-case\" requires a value"
+        );
+    }
+
+    #[test]
+    fn footer_references_pattern() {
+        let configured =
+            rules(r#"lint: { footer-references: { level: error, value: "^Refs: #\\d+" } }"#)
+                .unwrap();
+
+        assert_eq!(
+            configured.footer_references,
+            Some(Rule::new(
+                Level::Error,
+                Pattern::new(r"^Refs: #\d+").unwrap()
+            ))
+        );
+        assert!(
+            rules("lint: { footer-references: { level: error, value: \"(\" } }")
+                .unwrap_err()
+                .starts_with(
+                    "Invalid value for lint rule \"footer-references\"; \"(\" is not a valid regex"
+                )
+        );
+    }
+
+    fn scopes(value: &str, root: &Path) -> Result<Vec<String>, String> {
+        scope_list(&serde_yaml::from_str(value).unwrap(), root)
+    }
+
+    #[test]
+    fn scope_list_values() {
+        assert_eq!(
+            scopes("[parser, cli]", Path::new(".")).unwrap(),
+            vec!["parser", "cli"]
+        );
+        assert_eq!(
+            scopes("crates", Path::new(".")).unwrap_err(),
+            "\"crates\" is not a list of scopes, expected a list, \"workspace\" or \"directories\""
+        );
+        assert!(scopes("[parser, 1]", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn scope_list_workspace() {
+        let root = TempDir::new().unwrap();
+        root.child("Cargo.toml")
+            .write_str(
+                "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"core\", \"crates/*\"]\n",
+            )
+            .unwrap();
+        root.child("core/Cargo.toml")
+            .write_str("[package]\nname = \"app-core\"\n")
+            .unwrap();
+        root.child("crates/parser/Cargo.toml")
+            .write_str("[package]\nname = \"parser\"\n")
+            .unwrap();
+        root.child("crates/docs/README.md").touch().unwrap();
+
+        assert_eq!(
+            scopes("workspace", root.path()).unwrap(),
+            vec!["app", "app-core", "parser"]
+        );
+    }
+
+    #[test]
+    fn scope_list_workspace_without_manifest() {
+        let root = TempDir::new().unwrap();
+
+        assert!(scopes("workspace", root.path())
+            .unwrap_err()
+            .contains("Cargo.toml\" could not be read"));
+    }
+
+    #[test]
+    fn scope_list_directories() {
+        let root = TempDir::new().unwrap();
+        root.child("cli/main.rs").touch().unwrap();
+        root.child("parser/mod.rs").touch().unwrap();
+        root.child(".github/workflows").create_dir_all().unwrap();
+        root.child("target/debug").create_dir_all().unwrap();
+        root.child("README.md").touch().unwrap();
+
+        assert_eq!(
+            scopes("directories", root.path()).unwrap(),
+            vec!["cli", "parser"]
+        );
+    }
+
+    #[test]
+    fn scope_enum_rule() {
+        let root = TempDir::new().unwrap();
+        root.child("cli").create_dir_all().unwrap();
+        let config: Config =
+            serde_yaml::from_str("lint: { scope-enum: { level: error, value: directories } }")
+                .unwrap();
+
+        assert_eq!(
+            config.rules(root.path()).unwrap().scope_enum,
+            Some(Rule::new(Level::Error, vec![String::from("cli")]))
+        );
+    }
+}
@@ -1,12 +1,12 @@
 mod cli;
+mod config;
 
 use clap::Parser;
 
-use log::LevelFilter;
+use log::{error, LevelFilter};
 use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
 
 /// Simple program to automatically apply semantic versioning based on the conventional commits specification
-
 fn main() {
     let args = cli::Args::parse();
 
@@ -27,9 +27,23 @@ fn main() {
     )
     .unwrap();
 
+    let config = match config::Config::load(args.config.as_deref()) {
+        Ok(c) => c,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(cli::commands::INVALID_INPUT);
+        }
+    };
+
     match args.command {
-        cli::commands::Commands::Lint(args) => {
-            args.lint();
+        cli::Commands::Lint(args) => {
+            args.lint(&config);
+        }
+        cli::Commands::Commit(args) => {
+            args.commit(&config);
+        }
+        cli::Commands::Version(args) => {
+            args.version(&config);
         }
     }
 }
@@ -0,0 +1,132 @@
+mod common;
+
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+
+use common::{INVALID_INPUT, VIOLATIONS_FOUND};
+
+fn lint(repository: &assert_fs::TempDir, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("cocors")
+        .unwrap()
+        .args([
+            "lint",
+            "-p",
+            repository.path().to_str().unwrap(),
+            "-c",
+            "10",
+        ])
+        .args(args)
+        .assert()
+}
+
+/// The combined output of the command, log messages are printed to stdout and stderr
+fn output(assert: assert_cmd::assert::Assert) -> String {
+    let output = assert.get_output();
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn write_then_lint() {
+    let repository = common::repository(&["add parser", "feat: add parser", "remove typo"]);
+    let baseline = repository.child(".cocors-baseline");
+    let path = baseline.path().to_str().unwrap();
+
+    lint(&repository, &[]).code(VIOLATIONS_FOUND);
+    lint(&repository, &["--write-baseline", path]).success();
+
+    let hashes = common::git(&repository, &["log", "--format=%H", "--grep=^add parser$"]);
+    let content = std::fs::read_to_string(baseline.path()).unwrap();
+    assert!(content.contains(hashes.trim()));
+    assert_eq!(content.lines().filter(|l| !l.starts_with('#')).count(), 2);
+
+    lint(&repository, &["--baseline", path]).success();
+}
+
+#[test]
+fn report_new_violations() {
+    let repository = common::repository(&["add parser", "feat: add parser"]);
+    let baseline = repository.child(".cocors-baseline");
+    let path = baseline.path().to_str().unwrap();
+
+    lint(&repository, &["--write-baseline", path]).success();
+
+    repository.child("file").write_str("changed").unwrap();
+    common::git(
+        &repository,
+        &["commit", "--quiet", "-am", "update the readme"],
+    );
+    let new = common::git(&repository, &["rev-parse", "--short=7", "HEAD"]);
+
+    let output = output(lint(&repository, &["--baseline", path]).code(VIOLATIONS_FOUND));
+
+    assert!(output.contains("1 passed, 1 failed, 1 failed but are in the baseline"));
+    assert!(output.contains(new.trim()));
+}
+
+#[test]
+fn max_warnings_without_baseline() {
+    let repository = common::repository(&["feat: add parser.", "fix: remove typo"]);
+    let baseline = repository.child(".cocors-baseline");
+    let path = baseline.path().to_str().unwrap();
+
+    lint(
+        &repository,
+        &["--fail-on", "warning", "--write-baseline", path],
+    )
+    .success();
+
+    lint(&repository, &["--max-warnings", "0"]).code(VIOLATIONS_FOUND);
+    lint(
+        &repository,
+        &[
+            "--fail-on",
+            "warning",
+            "--baseline",
+            path,
+            "--max-warnings",
+            "0",
+        ],
+    )
+    .success();
+
+    repository.child("file").write_str("changed").unwrap();
+    common::git(
+        &repository,
+        &["commit", "--quiet", "-am", "docs: update readme."],
+    );
+
+    lint(&repository, &["--baseline", path, "--max-warnings", "0"]).code(VIOLATIONS_FOUND);
+}
+
+#[test]
+fn missing_baseline() {
+    let repository = common::repository(&["feat: add parser"]);
+    let baseline = repository.child("missing");
+
+    lint(
+        &repository,
+        &["--baseline", baseline.path().to_str().unwrap()],
+    )
+    .code(INVALID_INPUT);
+}
+
+#[test]
+fn corrupt_baseline() {
+    let repository = common::repository(&["add parser"]);
+    let baseline = repository.child(".cocors-baseline");
+    baseline.write_str("add parser\n").unwrap();
+
+    let output = output(
+        lint(
+            &repository,
+            &["--baseline", baseline.path().to_str().unwrap()],
+        )
+        .code(INVALID_INPUT),
+    );
+
+    assert!(output.contains("\"add parser\" is not a commit hash"));
+}
@@ -0,0 +1,47 @@
+// Shared by all test crates, not every crate uses every helper
+#![allow(dead_code)]
+
+use std::process::Command;
+
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+
+/// Exit code if violations at or above the `--fail-on` level have been found
+pub const VIOLATIONS_FOUND: i32 = 65;
+/// Exit code if the input is invalid
+pub const INVALID_INPUT: i32 = 66;
+/// Exit code if a git command failed
+pub const GIT_FAILURE: i32 = 69;
+
+/// Runs git in the directory and panics if it fails
+pub fn git(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed; {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Creates a repository in a temporary directory with a commit for every message
+pub fn repository(messages: &[&str]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(&dir, &["init", "--quiet"]);
+    git(&dir, &["config", "user.name", "cocors"]);
+    git(&dir, &["config", "user.email", "cocors@example.com"]);
+
+    for (i, message) in messages.iter().enumerate() {
+        dir.child("file").write_str(&i.to_string()).unwrap();
+        git(&dir, &["add", "file"]);
+        git(&dir, &["commit", "--quiet", "-m", message]);
+    }
+
+    dir
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+
+use common::{GIT_FAILURE, INVALID_INPUT, VIOLATIONS_FOUND};
+
+fn lint(args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("cocors")
+        .unwrap()
+        .arg("lint")
+        .args(args)
+        .assert()
+}
+
+#[test]
+fn valid_message() {
+    lint(&["-m", "feat(parser): add parser"]).success();
+}
+
+#[test]
+fn invalid_message() {
+    lint(&["-m", "add parser"]).code(VIOLATIONS_FOUND);
+}
+
+#[test]
+fn fail_on_level() {
+    // the message has a warning (subject-full-stop) and a suggestion (scope-missing)
+    let message = "feat: add parser.";
+
+    lint(&["-m", message]).success();
+    lint(&["-m", message, "--fail-on", "error"]).success();
+    lint(&["-m", message, "--fail-on", "warning"]).code(VIOLATIONS_FOUND);
+    lint(&["-m", message, "--fail-on", "info"]).code(VIOLATIONS_FOUND);
+    lint(&["-m", "feat: add parser", "--fail-on", "info"]).success();
+    lint(&["-m", "feat: add parser", "--fail-on", "suggestion"]).code(VIOLATIONS_FOUND);
+}
+
+#[test]
+fn max_warnings() {
+    lint(&["-m", "feat: add parser.", "--max-warnings", "1"]).success();
+    lint(&["-m", "feat: add parser.", "--max-warnings", "0"]).code(VIOLATIONS_FOUND);
+}
+
+#[test]
+fn max_warnings_across_commits() {
+    let repository =
+        common::repository(&["feat: add parser.", "fix: remove typo.", "docs: add readme"]);
+    let path = repository.path().to_str().unwrap();
+
+    lint(&["-p", path, "-c", "3", "--max-warnings", "2"]).success();
+    lint(&["-p", path, "-c", "3", "--max-warnings", "1"]).code(VIOLATIONS_FOUND);
+}
+
+#[test]
+fn report_ignored_rules() {
+    let message = "feat: add parser.\n\nCocors-Ignore: subject-full-stop";
+
+    let output = lint(&["-m", message]).success().get_output().stdout.clone();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Ignored by Cocors-Ignore: [subject-full-stop]"));
+
+    let output = lint(&["-m", message, "--format", "json"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(report["commits"][0]["outcome"], "passed");
+    assert_eq!(
+        report["commits"][0]["ignored"][0]["rule"],
+        "subject-full-stop"
+    );
+    assert_eq!(report["summary"]["ignored"]["subject-full-stop"], 1);
+}
+
+#[test]
+fn json_report() {
+    let repository = common::repository(&["feat: add parser", "add parser"]);
+    let output = lint(&[
+        "-p",
+        repository.path().to_str().unwrap(),
+        "-c",
+        "2",
+        "--format",
+        "json",
+    ])
+    .code(VIOLATIONS_FOUND)
+    .get_output()
+    .stdout
+    .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let head = common::git(&repository, &["rev-parse", "HEAD"]);
+
+    assert_eq!(report["commits"][0]["hash"], head.trim_end());
+    assert_eq!(report["commits"][0]["subject"], "add parser");
+    assert_eq!(report["commits"][0]["outcome"], "failed");
+    assert_eq!(report["commits"][1]["outcome"], "passed");
+    assert_eq!(report["summary"]["checked"], 2);
+    assert_eq!(report["summary"]["failed"], 1);
+}
+
+#[test]
+fn invalid_input() {
+    let dir = TempDir::new().unwrap();
+
+    lint(&["-p", dir.path().to_str().unwrap()]).code(INVALID_INPUT);
+    lint(&["-e", dir.child("COMMIT_EDITMSG").path().to_str().unwrap()]).code(INVALID_INPUT);
+}
+
+#[test]
+fn invalid_configuration() {
+    let dir = TempDir::new().unwrap();
+    let config = dir.child("cocors.yaml");
+    config
+        .write_str("lint: { footer-references: { level: error, value: \"(\" } }")
+        .unwrap();
+
+    lint(&[
+        "--config",
+        config.path().to_str().unwrap(),
+        "-m",
+        "feat: add parser",
+    ])
+    .code(INVALID_INPUT);
+
+    config.write_str("lint: [").unwrap();
+    lint(&[
+        "--config",
+        config.path().to_str().unwrap(),
+        "-m",
+        "feat: add parser",
+    ])
+    .code(INVALID_INPUT);
+}
+
+// The gix backend does not run the git binary, so it can not be hidden from it
+#[cfg(not(feature = "gix"))]
+#[test]
+fn git_failure() {
+    let repository = common::repository(&["feat: add parser"]);
+
+    Command::cargo_bin("cocors")
+        .unwrap()
+        .env("PATH", "")
+        .args(["lint", "-p", repository.path().to_str().unwrap()])
+        .assert()
+        .code(GIT_FAILURE);
+}
+
+#[test]
+fn corrupt_repository() {
+    let repository = common::repository(&["feat: add parser", "fix: remove typo"]);
+    let parent = common::git(&repository, &["rev-parse", "HEAD~1"]);
+    let (directory, file) = parent.trim_end().split_at(2);
+
+    std::fs::remove_file(
+        repository
+            .path()
+            .join(".git/objects")
+            .join(directory)
+            .join(file),
+    )
+    .unwrap();
+
+    lint(&["-p", repository.path().to_str().unwrap(), "-c", "2"]).code(GIT_FAILURE);
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use assert_cmd::Command;
+use assert_fs::prelude::*;
+
+use common::{GIT_FAILURE, INVALID_INPUT};
+
+fn version(args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("cocors")
+        .unwrap()
+        .arg("version")
+        .args(args)
+        .assert()
+}
+
+fn stdout(assert: assert_cmd::assert::Assert) -> String {
+    String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+}
+
+/// A repository with the release `v1.2.0` followed by a feature
+fn released() -> assert_fs::TempDir {
+    let repository = common::repository(&["feat: first"]);
+    common::git(&repository, &["tag", "-a", "v1.2.0", "-m", "1.2.0"]);
+    common::git(
+        &repository,
+        &["commit", "--quiet", "--allow-empty", "-m", "feat: second"],
+    );
+    repository
+}
+
+#[test]
+fn next_without_subcommand() {
+    let repository = released();
+    let path = repository.path().to_str().unwrap();
+
+    for args in [
+        vec!["-p", path, "-q"],
+        vec!["-p", path, "next", "-q"],
+        vec!["next", "-p", path, "-q"],
+    ] {
+        assert_eq!(stdout(version(&args).success()), "1.3.0\n", "{:?}", args);
+    }
+
+    version(&["-p", path, "--dev", "next"]).code(64);
+
+    let dev = stdout(version(&["-p", path, "--dev", "-q"]).success());
+    assert!(dev.starts_with("1.3.0-alpha.1+g"), "{}", dev);
+    assert_eq!(
+        stdout(version(&["-p", path, "--metadata", "build.{commit_count}", "-q"]).success()),
+        "1.3.0+build.1\n"
+    );
+}
+
+#[test]
+fn invalid_metadata() {
+    let repository = released();
+
+    version(&[
+        "-p",
+        repository.path().to_str().unwrap(),
+        "--metadata",
+        "{unknown}",
+    ])
+    .code(INVALID_INPUT);
+}
+
+#[test]
+fn compare() {
+    assert_eq!(
+        stdout(version(&["compare", "v1.2.0", "1.10.0"]).success()),
+        "less\n"
+    );
+    assert_eq!(
+        stdout(version(&["compare", "1.2.0+a", "1.2.0+b"]).success()),
+        "equal\n"
+    );
+    assert_eq!(
+        stdout(version(&["compare", "1.2.0", "1.2.0-rc.1"]).success()),
+        "greater\n"
+    );
+}
+
+#[test]
+fn compare_invalid_version() {
+    version(&["compare", "1.2", "1.2.0"]).code(INVALID_INPUT);
+    version(&["compare", "1.2.0", "1.02.0"]).code(INVALID_INPUT);
+    version(&["compare", "1.2.0", "release-1.2.0"]).code(INVALID_INPUT);
+}
+
+#[test]
+fn invalid_channel() {
+    let repository = released();
+    let config = repository.child("cocors.yaml");
+    config
+        .write_str("channels: [{ branch: main, bump: huge }]")
+        .unwrap();
+
+    version(&[
+        "--config",
+        config.path().to_str().unwrap(),
+        "-p",
+        repository.path().to_str().unwrap(),
+        "--branch",
+        "main",
+    ])
+    .code(INVALID_INPUT);
+}
+
+#[test]
+fn deepen_shallow_clone() {
+    let repository = common::repository(&["feat: first", "fix: second", "fix: third"]);
+    common::git(&repository, &["tag", "v1.2.0", "HEAD~2"]);
+    common::git(
+        &repository,
+        &["tag", "-a", "reviewed", "HEAD~1", "-m", "reviewed"],
+    );
+
+    let clone = assert_fs::TempDir::new().unwrap();
+    common::git(
+        &clone,
+        &[
+            "clone",
+            "--quiet",
+            "--depth",
+            "1",
+            &format!("file://{}", repository.path().display()),
+            ".",
+        ],
+    );
+    let path = clone.path().to_str().unwrap();
+
+    version(&["-p", path, "current"]).code(GIT_FAILURE);
+    assert_eq!(
+        stdout(version(&["-p", path, "current", "--deepen"]).success()),
+        "1.2.0\n"
+    );
+    assert_eq!(
+        stdout(version(&["-p", path, "-q", "--deepen"]).success()),
+        "1.2.1\n"
+    );
+}